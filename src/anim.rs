@@ -0,0 +1,107 @@
+use std::fmt;
+#[cfg(feature = "viz")]
+use std::io::{self, prelude::*};
+#[cfg(feature = "viz")]
+use std::thread;
+#[cfg(feature = "viz")]
+use std::time::{Duration, Instant};
+
+/// A snapshot of a simulation that can be drawn to the terminal.
+pub trait Frame {
+    fn draw(&self, f: &mut dyn fmt::Write) -> fmt::Result;
+}
+
+/// Redraws frames in place in the terminal, throttled to a frame rate.
+///
+/// A disabled animator ignores all frames, so solutions can call it
+/// unconditionally.
+#[cfg(feature = "viz")]
+pub struct Animator {
+    enabled: bool,
+    frame_time: Duration,
+    skip: usize,
+    steps: usize,
+    last_height: usize,
+    last_draw: Option<Instant>,
+}
+
+#[cfg(feature = "viz")]
+impl Animator {
+    pub fn new(fps: u32, skip: usize) -> Self {
+        Self {
+            enabled: true,
+            frame_time: Duration::from_secs(1) / fps.max(1),
+            skip: skip.max(1),
+            steps: 0,
+            last_height: 0,
+            last_draw: None,
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::new(1, 1)
+        }
+    }
+
+    /// Advances the simulation one step, only drawing every `skip`th step.
+    pub fn step(&mut self, frame: &dyn Frame) {
+        if !self.enabled {
+            return;
+        }
+        self.steps += 1;
+        if (self.steps - 1).is_multiple_of(self.skip) {
+            self.draw(frame);
+        }
+    }
+
+    /// Draws the final state, regardless of how many steps were skipped.
+    pub fn finish(&mut self, frame: &dyn Frame) {
+        if self.enabled {
+            self.draw(frame);
+        }
+    }
+
+    fn draw(&mut self, frame: &dyn Frame) {
+        let mut buf = String::new();
+        frame.draw(&mut buf).expect("Draw animation frame");
+        if !buf.ends_with('\n') {
+            buf.push('\n');
+        }
+
+        if let Some(last_draw) = self.last_draw {
+            let elapsed = last_draw.elapsed();
+            if elapsed < self.frame_time {
+                thread::sleep(self.frame_time - elapsed);
+            }
+        }
+
+        let mut out = io::stdout().lock();
+        if self.last_height > 0 {
+            // Move the cursor back up to where the previous frame started
+            write!(out, "\x1b[{}A", self.last_height).unwrap();
+        }
+        write!(out, "\x1b[J{}", buf).unwrap();
+        out.flush().unwrap();
+
+        self.last_height = buf.lines().count();
+        self.last_draw = Some(Instant::now());
+    }
+}
+
+/// Without the `viz` feature there's nothing to draw with, so animators are
+/// always disabled.
+#[cfg(not(feature = "viz"))]
+pub struct Animator;
+
+#[cfg(not(feature = "viz"))]
+impl Animator {
+    pub fn disabled() -> Self {
+        Self
+    }
+
+    pub fn step(&mut self, _frame: &dyn Frame) {}
+
+    pub fn finish(&mut self, _frame: &dyn Frame) {}
+}
@@ -0,0 +1,116 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::day::{Day, Part};
+
+/// What's in an artifact, which picks its file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Text,
+    Csv,
+    Svg,
+    Json,
+}
+
+impl Kind {
+    fn extension(&self) -> &'static str {
+        match self {
+            Kind::Text => "txt",
+            Kind::Csv => "csv",
+            Kind::Svg => "svg",
+            Kind::Json => "json",
+        }
+    }
+}
+
+/// Something a solution produces besides its answer, like a picture or a
+/// listing of intermediate results.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Artifact {
+    pub name: String,
+    pub kind: Kind,
+    pub contents: String,
+}
+
+impl Artifact {
+    /// Like `day10-part2-crt.txt`.
+    pub fn file_name(&self, day: Day, part: Part) -> String {
+        format!("{}-{}-{}.{}", day, part, self.name, self.kind.extension())
+    }
+}
+
+/// Collects the artifacts a solution emits, shown with `--artifacts`.
+///
+/// Disabled artifacts ignore everything emitted without building it, so
+/// solutions can emit unconditionally.
+pub struct Artifacts {
+    collected: Option<Vec<Artifact>>,
+}
+
+impl Artifacts {
+    pub fn collecting() -> Self {
+        Self {
+            collected: Some(vec![]),
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self { collected: None }
+    }
+
+    /// Adds an artifact, only calling `contents` when collecting.
+    pub fn emit(&mut self, name: &str, kind: Kind, contents: impl FnOnce() -> String) {
+        if let Some(collected) = &mut self.collected {
+            collected.push(Artifact {
+                name: name.to_string(),
+                kind,
+                contents: contents(),
+            });
+        }
+    }
+
+    /// Takes the artifacts emitted so far.
+    pub fn take(&mut self) -> Vec<Artifact> {
+        self.collected.as_mut().map_or(vec![], std::mem::take)
+    }
+}
+
+/// Writes a day and part's artifacts to `dir`, creating it if needed, and
+/// returns the paths written.
+pub fn write(dir: &str, day: Day, part: Part, artifacts: &[Artifact]) -> io::Result<Vec<String>> {
+    if artifacts.is_empty() {
+        return Ok(vec![]);
+    }
+    fs::create_dir_all(dir)?;
+    let mut paths = vec![];
+    for artifact in artifacts {
+        let path = Path::new(dir).join(artifact.file_name(day, part));
+        fs::write(&path, &artifact.contents)?;
+        paths.push(path.display().to_string());
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::artifact::{Artifacts, Kind};
+    use crate::day::{Day, Part};
+
+    #[test]
+    fn test_artifacts() {
+        let mut disabled = Artifacts::disabled();
+        disabled.emit("never", Kind::Text, || panic!("Built a disabled artifact"));
+        assert!(disabled.take().is_empty());
+
+        let mut artifacts = Artifacts::collecting();
+        artifacts.emit("sizes", Kind::Csv, || "dir,size\n/,48381165\n".to_string());
+        let taken = artifacts.take();
+        assert_eq!(taken.len(), 1);
+        assert_eq!(
+            taken[0].file_name(Day::Day07, Part::Part1),
+            "day07-part1-sizes.csv"
+        );
+        assert!(artifacts.take().is_empty());
+    }
+}
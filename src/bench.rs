@@ -0,0 +1,106 @@
+use std::time::Duration;
+
+use crate::day::{Context, Day, Part};
+use crate::history::{self, Record, BENCH_HISTORY_PATH};
+use crate::input::Input;
+use crate::runner::{self, format_duration, Outcome};
+use crate::time;
+
+/// Runs each day and part `runs` times on its input, printing the fastest
+/// and median runtimes, and the median parse and solve times.
+///
+/// With `record`, the results are also appended to [`BENCH_HISTORY_PATH`].
+pub fn run(days: &[Day], runs: usize, record: bool) {
+    println!(
+        "{:<11}  {:>10} {:>10} {:>10} {:>10}",
+        "", "min", "median", "parse", "solve"
+    );
+    let commit = history::current_commit();
+    let timestamp = time::now();
+    let records: Vec<Record> = runner::with_quiet_panics(|| {
+        let mut records = vec![];
+        for &day in days {
+            for &part in Part::all() {
+                records.extend(bench(day, part, runs, &commit, timestamp));
+            }
+        }
+        records
+    });
+
+    if record {
+        match history::append(BENCH_HISTORY_PATH, &records) {
+            Ok(()) => println!(
+                "Recorded {} result(s) for {} in {}",
+                records.len(),
+                commit,
+                BENCH_HISTORY_PATH
+            ),
+            Err(e) => eprintln!("Couldn't record to {}: {}", BENCH_HISTORY_PATH, e),
+        }
+    }
+}
+
+fn bench(day: Day, part: Part, runs: usize, commit: &str, timestamp: u64) -> Option<Record> {
+    let mut results = vec![];
+    for _ in 0..runs {
+        let ctx = Context::captured(Input::File(day.input_path()));
+        let result = runner::run_isolated(day, part, ctx, None);
+        if !matches!(result.outcome, Outcome::Answer(_)) {
+            println!("{} {}  {}", day, part, result.outcome);
+            return None;
+        }
+        results.push(result);
+    }
+
+    let totals: Vec<Duration> = results.iter().map(|r| r.duration).collect();
+    let phases: Option<Vec<_>> = results.iter().map(|r| r.phases).collect();
+    let (parse, solve) = match phases {
+        Some(phases) => (
+            Some(median(phases.iter().map(|p| p.parse).collect())),
+            Some(median(phases.iter().map(|p| p.solve).collect())),
+        ),
+        None => (None, None),
+    };
+    let record = Record {
+        commit: commit.to_string(),
+        timestamp,
+        day,
+        part,
+        runs,
+        min: *totals.iter().min().unwrap(),
+        median: median(totals),
+        parse,
+        solve,
+    };
+    let optional = |d: Option<Duration>| d.map_or(String::new(), format_duration);
+    let line = format!(
+        "{} {}  {:>10} {:>10} {:>10} {:>10}",
+        day,
+        part,
+        format_duration(record.min),
+        format_duration(record.median),
+        optional(record.parse),
+        optional(record.solve)
+    );
+    println!("{}", line.trim_end());
+    Some(record)
+}
+
+fn median(mut durations: Vec<Duration>) -> Duration {
+    durations.sort();
+    durations[durations.len() / 2]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::bench::median;
+
+    #[test]
+    fn test_median() {
+        let ms = Duration::from_millis;
+        assert_eq!(median(vec![ms(3), ms(1), ms(2)]), ms(2));
+        assert_eq!(median(vec![ms(5)]), ms(5));
+    }
+}
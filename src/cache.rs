@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use clap::ValueEnum;
+
+use crate::day::{Day, Part};
+
+pub const CACHE_PATH: &str = ".cache/answers.txt";
+
+/// Answers from earlier runs, so unchanged solutions on unchanged inputs
+/// don't need to be solved again.
+///
+/// Stored one per line as `day01 part1 <input hash> <version> <µs> <answer>`.
+/// Only the latest answer for each day and part is kept.
+#[derive(Debug, Default)]
+pub struct Cache(HashMap<(Day, Part), Entry>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub input_hash: u64,
+    pub version: String,
+    pub answer: String,
+    pub duration: Duration,
+}
+
+impl Cache {
+    /// Reads the cache, starting over if it's missing or unreadable.
+    pub fn load(path: &str) -> Cache {
+        fs::read_to_string(path)
+            .map(|s| Cache::parse(&s))
+            .unwrap_or_default()
+    }
+
+    fn parse(s: &str) -> Cache {
+        let mut entries = HashMap::new();
+        for line in s.lines() {
+            // Anything unexpected is just a cache miss
+            if let Some((key, entry)) = Cache::parse_line(line) {
+                entries.insert(key, entry);
+            }
+        }
+        Cache(entries)
+    }
+
+    fn parse_line(line: &str) -> Option<((Day, Part), Entry)> {
+        let mut fields = line.splitn(6, ' ');
+        let day = Day::from_str(fields.next()?, true).ok()?;
+        let part = Part::from_str(fields.next()?, true).ok()?;
+        let input_hash = u64::from_str_radix(fields.next()?, 16).ok()?;
+        let version = fields.next()?.to_string();
+        let micros = fields.next()?.parse().ok()?;
+        let answer = fields.next()?.to_string();
+        let entry = Entry {
+            input_hash,
+            version,
+            answer,
+            duration: Duration::from_micros(micros),
+        };
+        Some(((day, part), entry))
+    }
+
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        if let Some(dir) = Path::new(path).parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut keys: Vec<_> = self.0.keys().collect();
+        keys.sort();
+        let mut s = String::new();
+        for key @ (day, part) in keys {
+            let entry = &self.0[key];
+            s += &format!(
+                "{} {} {:016x} {} {} {}\n",
+                day,
+                part,
+                entry.input_hash,
+                entry.version,
+                entry.duration.as_micros(),
+                entry.answer
+            );
+        }
+        fs::write(path, s)
+    }
+
+    /// Looks up an answer, if there's one for this exact input and version.
+    pub fn get(&self, day: Day, part: Part, input_hash: u64, version: &str) -> Option<&Entry> {
+        self.0
+            .get(&(day, part))
+            .filter(|e| e.input_hash == input_hash && e.version == version)
+    }
+
+    pub fn insert(&mut self, day: Day, part: Part, entry: Entry) {
+        self.0.insert((day, part), entry);
+    }
+
+    #[cfg(feature = "tui")]
+    pub fn remove(&mut self, day: Day, part: Part) {
+        self.0.remove(&(day, part));
+    }
+}
+
+/// 64-bit FNV-1a, which unlike the std hashers is stable between runs and
+/// Rust versions.
+pub fn hash(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for &b in bytes {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::cache::{hash, Cache, Entry};
+    use crate::day::{Day, Part};
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_ne!(hash(b"ab"), hash(b"ba"));
+    }
+
+    #[test]
+    fn test_parse_cache() {
+        let cache =
+            Cache::parse("day05 part1 00000000000000ff abc 1234 PTWLTDSJV\nday01 part1 garbage\n");
+        let entry = Entry {
+            input_hash: 0xff,
+            version: "abc".to_string(),
+            answer: "PTWLTDSJV".to_string(),
+            duration: Duration::from_micros(1234),
+        };
+        assert_eq!(
+            cache.get(Day::Day05, Part::Part1, 0xff, "abc"),
+            Some(&entry)
+        );
+        assert_eq!(cache.get(Day::Day05, Part::Part1, 0xfe, "abc"), None);
+        assert_eq!(cache.get(Day::Day05, Part::Part1, 0xff, "abd"), None);
+        assert_eq!(cache.get(Day::Day01, Part::Part1, 0xff, "abc"), None);
+    }
+}
@@ -0,0 +1,133 @@
+use std::fmt::Display;
+
+use crate::crypt;
+use crate::day::Day;
+
+/// Something wrong with an input file, optionally pointing at a line.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Problem {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl Problem {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            line: None,
+            message: message.into(),
+        }
+    }
+
+    /// Creates a problem for the line at the zero-based `index`.
+    pub fn at(index: usize, message: impl Into<String>) -> Self {
+        Self {
+            line: Some(index + 1),
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Checks the input file for a day without solving it, printing every
+/// problem found. Exits with a non-zero code if there were any problems.
+pub fn run(day: Day, file_path: &str) {
+    println!(">>> check {}", day);
+    println!(">>> file: {}", file_path);
+
+    let problems = match crypt::read_input(file_path) {
+        Ok(bytes) => check_bytes(day, &bytes),
+        Err(err) => vec![Problem::new(err)],
+    };
+
+    for problem in &problems {
+        println!("{}", problem);
+    }
+    if problems.is_empty() {
+        println!("No problems found");
+    } else {
+        println!("Found {} problem(s)", problems.len());
+        std::process::exit(1);
+    }
+}
+
+pub fn check_bytes(day: Day, bytes: &[u8]) -> Vec<Problem> {
+    let input = match std::str::from_utf8(bytes) {
+        Ok(input) => input,
+        Err(err) => return vec![Problem::new(format!("not valid UTF-8: {}", err))],
+    };
+
+    let mut problems = check_common(input);
+    if input.is_empty() {
+        return problems;
+    }
+    problems.extend(day.check(input));
+    problems
+}
+
+/// Checks that apply to the input of every day.
+fn check_common(input: &str) -> Vec<Problem> {
+    let mut problems = vec![];
+    if input.is_empty() {
+        problems.push(Problem::new("file is empty"));
+        return problems;
+    }
+
+    let crlf_lines: Vec<usize> = input
+        .split_inclusive('\n')
+        .enumerate()
+        .filter(|(_, line)| line.ends_with("\r\n"))
+        .map(|(i, _)| i)
+        .collect();
+    match crlf_lines.as_slice() {
+        [] => (),
+        [first, ..] if crlf_lines.len() > 1 => problems.push(Problem::at(
+            *first,
+            format!("CRLF line ending (and on {} more lines)", crlf_lines.len() - 1),
+        )),
+        [first, ..] => problems.push(Problem::at(*first, "CRLF line ending")),
+    }
+
+    if !input.ends_with('\n') {
+        problems.push(Problem::new("missing newline at end of file"));
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check::{check_common, Problem};
+
+    #[test]
+    fn test_check_common_ok() {
+        assert_eq!(check_common("a\nb\n"), vec![]);
+    }
+
+    #[test]
+    fn test_check_common_empty() {
+        assert_eq!(check_common(""), vec![Problem::new("file is empty")]);
+    }
+
+    #[test]
+    fn test_check_common_crlf() {
+        assert_eq!(
+            check_common("a\nb\r\nc\r\n"),
+            vec![Problem::at(1, "CRLF line ending (and on 1 more lines)")]
+        );
+    }
+
+    #[test]
+    fn test_check_common_truncated() {
+        assert_eq!(
+            check_common("a\nb"),
+            vec![Problem::new("missing newline at end of file")]
+        );
+    }
+}
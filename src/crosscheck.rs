@@ -0,0 +1,47 @@
+use crate::day::{Context, Day, Part};
+use crate::input::Input;
+use crate::runner::{self, format_duration};
+
+/// Runs every implementation of a day on the same input and reports any
+/// parts where they don't agree. Exits with a non-zero code if they don't.
+pub fn run(day: Day, input: Input) {
+    let impls = day.impls();
+    if impls.len() < 2 {
+        println!(
+            "{} has only one implementation, nothing to cross-check",
+            day
+        );
+        return;
+    }
+    println!(">>> {}, input: {}", day, input);
+
+    let mut disagreements = 0;
+    runner::with_quiet_panics(|| {
+        for &part in Part::all() {
+            let mut outcomes = vec![];
+            for &name in impls {
+                let mut ctx = Context::captured(input.clone());
+                ctx.impl_name = Some(name.to_string());
+                let result = runner::run_isolated(day, part, ctx, None);
+                println!(
+                    "{} {}  {:<10} {:<20} {:>10}",
+                    day,
+                    part,
+                    name,
+                    result.outcome.to_string(),
+                    format_duration(result.duration)
+                );
+                outcomes.push(result.outcome);
+            }
+            if outcomes.iter().any(|o| *o != outcomes[0]) {
+                println!("    implementations disagree on {}", part);
+                disagreements += 1;
+            }
+        }
+    });
+
+    if disagreements > 0 {
+        std::process::exit(1);
+    }
+    println!("All implementations agree");
+}
@@ -0,0 +1,156 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+use crate::day::Day;
+
+/// Environment variable holding the key for encrypted inputs, as 64 hex digits.
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+
+/// Extension of encrypted input files, as in `inputs/day07.txt.enc`.
+pub const EXTENSION: &str = "enc";
+
+/// Start of every encrypted file, so that a wrong file fails clearly.
+const MAGIC: &[u8] = b"aoc-enc1";
+
+const NONCE_LEN: usize = 12;
+
+pub fn encrypted_path(path: &str) -> String {
+    format!("{}.{}", path, EXTENSION)
+}
+
+/// Reads the key from the environment.
+pub fn key_from_env() -> Result<Key, String> {
+    match env::var(KEY_VAR) {
+        Ok(hex) if !hex.is_empty() => parse_key(&hex),
+        _ => Err(format!(
+            "{} is not set. Ask the team for the input key, or make a new one with `openssl rand -hex 32`",
+            KEY_VAR
+        )),
+    }
+}
+
+fn parse_key(hex: &str) -> Result<Key, String> {
+    let hex = hex.trim();
+    if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("{} must be 64 hex digits", KEY_VAR));
+    }
+    let bytes: Vec<u8> = (0..64)
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect();
+    Ok(*Key::from_slice(&bytes))
+}
+
+pub fn encrypt(key: &Key, plaintext: &[u8]) -> Vec<u8> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = ChaCha20Poly1305::new(key)
+        .encrypt(&nonce, plaintext)
+        .expect("Encrypt the input");
+    [MAGIC, nonce.as_slice(), &ciphertext].concat()
+}
+
+pub fn decrypt(key: &Key, data: &[u8]) -> Result<Vec<u8>, String> {
+    let data = data
+        .strip_prefix(MAGIC)
+        .filter(|d| d.len() >= NONCE_LEN)
+        .ok_or("not an encrypted input")?;
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    ChaCha20Poly1305::new(key)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| format!("wrong {} or corrupted file", KEY_VAR))
+}
+
+/// Reads an input file, decrypting `<path>.enc` instead if there's only
+/// the encrypted one.
+pub fn read_input(path: &str) -> Result<Vec<u8>, String> {
+    let encrypted = encrypted_path(path);
+    if Path::new(path).exists() || !Path::new(&encrypted).exists() {
+        return fs::read(path).map_err(|e| format!("cannot read {}: {}", path, e));
+    }
+    let key = key_from_env().map_err(|e| format!("{} is encrypted, but {}", encrypted, e))?;
+    let data = fs::read(&encrypted).map_err(|e| format!("cannot read {}: {}", encrypted, e))?;
+    decrypt(&key, &data).map_err(|e| format!("cannot decrypt {}: {}", encrypted, e))
+}
+
+/// Encrypts every day's input that exists, skipping those whose encrypted
+/// file is already up to date so they don't show up as changed.
+pub fn encrypt_inputs() {
+    let key = key_from_env().unwrap_or_else(|e| exit_with(&e));
+    for day in Day::all() {
+        let path = day.input_path();
+        let Ok(plaintext) = fs::read(&path) else {
+            continue;
+        };
+        let encrypted = encrypted_path(&path);
+        let unchanged = fs::read(&encrypted)
+            .ok()
+            .and_then(|data| decrypt(&key, &data).ok())
+            .is_some_and(|old| old == plaintext);
+        if unchanged {
+            println!("{}: unchanged", encrypted);
+            continue;
+        }
+        fs::write(&encrypted, encrypt(&key, &plaintext)).expect("Write the encrypted input");
+        println!("{}: encrypted", encrypted);
+    }
+}
+
+/// Decrypts every day's encrypted input, overwriting the plain files.
+pub fn decrypt_inputs() {
+    let key = key_from_env().unwrap_or_else(|e| exit_with(&e));
+    for day in Day::all() {
+        let path = day.input_path();
+        let encrypted = encrypted_path(&path);
+        let Ok(data) = fs::read(&encrypted) else {
+            continue;
+        };
+        match decrypt(&key, &data) {
+            Ok(plaintext) => {
+                fs::write(&path, plaintext).expect("Write the decrypted input");
+                println!("{}: decrypted", path);
+            }
+            Err(e) => exit_with(&format!("cannot decrypt {}: {}", encrypted, e)),
+        }
+    }
+}
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("error: {}", message);
+    std::process::exit(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::crypt::{decrypt, encrypt, parse_key};
+
+    const KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let key = parse_key(KEY).unwrap();
+        let data = encrypt(&key, b"30373\n25512\n");
+        assert_eq!(decrypt(&key, &data), Ok(b"30373\n25512\n".to_vec()));
+        assert_ne!(encrypt(&key, b"30373\n25512\n"), data);
+
+        let other_key = parse_key(&KEY.replace('0', "f")).unwrap();
+        assert!(decrypt(&other_key, &data).is_err());
+        let mut tampered = data.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(decrypt(&key, &tampered).is_err());
+        assert_eq!(
+            decrypt(&key, b"30373\n"),
+            Err("not an encrypted input".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_key() {
+        assert!(parse_key(KEY).is_ok());
+        assert!(parse_key(&KEY[1..]).is_err());
+        assert!(parse_key(&KEY.replace('a', "g")).is_err());
+    }
+}
@@ -1,8 +1,41 @@
-use std::fmt::Display;
+use std::fmt::{self, Display, Write as _};
+use std::io::{self, Write as _};
+use std::time::Instant;
 
 use clap::ValueEnum;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+use crate::anim::Animator;
+use crate::artifact::Artifacts;
+use crate::cache;
+use crate::check::Problem;
+use crate::generate::Rng;
+use crate::input::Input;
+use crate::params::{Param, Params};
+use crate::trace::Trace;
+#[cfg(feature = "day01")]
+use crate::day01;
+#[cfg(feature = "day02")]
+use crate::day02;
+#[cfg(feature = "day03")]
+use crate::day03;
+#[cfg(feature = "day04")]
+use crate::day04;
+#[cfg(feature = "day05")]
+use crate::day05;
+#[cfg(feature = "day06")]
+use crate::day06;
+#[cfg(feature = "day07")]
+use crate::day07;
+#[cfg(feature = "day08")]
+use crate::day08;
+#[cfg(feature = "day09")]
+use crate::day09;
+#[cfg(feature = "day10")]
+use crate::day10;
+#[cfg(feature = "day11")]
+use crate::day11;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, ValueEnum)]
 pub enum Day {
     Day01,
     Day02,
@@ -17,20 +50,294 @@ pub enum Day {
     Day11,
 }
 
+/// What the solutions run on besides their own source, see [`Day::version`].
+const SHARED_SOURCES: &[&str] = &[
+    include_str!("anim.rs"),
+    include_str!("artifact.rs"),
+    include_str!("check.rs"),
+    include_str!("day.rs"),
+    include_str!("generate.rs"),
+    include_str!("input.rs"),
+    include_str!("json.rs"),
+    include_str!("params.rs"),
+    include_str!("runner.rs"),
+    include_str!("trace.rs"),
+];
+
+impl Day {
+    /// The days compiled in, see [`Day::is_compiled`].
+    pub fn all() -> &'static [Day] {
+        &[
+            #[cfg(feature = "day01")]
+            Day::Day01,
+            #[cfg(feature = "day02")]
+            Day::Day02,
+            #[cfg(feature = "day03")]
+            Day::Day03,
+            #[cfg(feature = "day04")]
+            Day::Day04,
+            #[cfg(feature = "day05")]
+            Day::Day05,
+            #[cfg(feature = "day06")]
+            Day::Day06,
+            #[cfg(feature = "day07")]
+            Day::Day07,
+            #[cfg(feature = "day08")]
+            Day::Day08,
+            #[cfg(feature = "day09")]
+            Day::Day09,
+            #[cfg(feature = "day10")]
+            Day::Day10,
+            #[cfg(feature = "day11")]
+            Day::Day11,
+        ]
+    }
+
+    /// Whether the day's solution is part of this build. Each day has a
+    /// Cargo feature named after it, all enabled by default.
+    pub fn is_compiled(&self) -> bool {
+        Day::all().contains(self)
+    }
+
+    /// The Cargo feature that compiles in the day's solution.
+    pub fn feature(&self) -> String {
+        self.to_string()
+    }
+
+    fn not_compiled(&self) -> ! {
+        panic!(
+            "{} isn't compiled in, build with --features {}",
+            self,
+            self.feature()
+        )
+    }
+
+    /// Runs the solution, returning its answer, or `None` if the part
+    /// hasn't been solved yet.
+    pub fn run(&self, part: Part, ctx: &mut Context) -> Option<String> {
+        ctx.params.fill_defaults(self.params());
+        match self {
+            #[cfg(feature = "day01")]
+            Day::Day01 => day01::run(part, ctx),
+            #[cfg(feature = "day02")]
+            Day::Day02 => day02::run(part, ctx),
+            #[cfg(feature = "day03")]
+            Day::Day03 => day03::run(part, ctx),
+            #[cfg(feature = "day04")]
+            Day::Day04 => day04::run(part, ctx),
+            #[cfg(feature = "day05")]
+            Day::Day05 => day05::run(part, ctx),
+            #[cfg(feature = "day06")]
+            Day::Day06 => day06::run(part, ctx),
+            #[cfg(feature = "day07")]
+            Day::Day07 => day07::run(part, ctx),
+            #[cfg(feature = "day08")]
+            Day::Day08 => day08::run(part, ctx),
+            #[cfg(feature = "day09")]
+            Day::Day09 => day09::run(part, ctx),
+            #[cfg(feature = "day10")]
+            Day::Day10 => day10::run(part, ctx),
+            #[cfg(feature = "day11")]
+            Day::Day11 => day11::run(part, ctx),
+            #[allow(unreachable_patterns)]
+            _ => self.not_compiled(),
+        }
+    }
+
+    /// Checks the structure of an input without solving it.
+    pub fn check(&self, input: &str) -> Vec<Problem> {
+        match self {
+            #[cfg(feature = "day01")]
+            Day::Day01 => day01::check(input),
+            #[cfg(feature = "day02")]
+            Day::Day02 => day02::check(input),
+            #[cfg(feature = "day03")]
+            Day::Day03 => day03::check(input),
+            #[cfg(feature = "day04")]
+            Day::Day04 => day04::check(input),
+            #[cfg(feature = "day05")]
+            Day::Day05 => day05::check(input),
+            #[cfg(feature = "day06")]
+            Day::Day06 => day06::check(input),
+            #[cfg(feature = "day07")]
+            Day::Day07 => day07::check(input),
+            #[cfg(feature = "day08")]
+            Day::Day08 => day08::check(input),
+            #[cfg(feature = "day09")]
+            Day::Day09 => day09::check(input),
+            #[cfg(feature = "day10")]
+            Day::Day10 => day10::check(input),
+            #[cfg(feature = "day11")]
+            Day::Day11 => day11::check(input),
+            #[allow(unreachable_patterns)]
+            _ => self.not_compiled(),
+        }
+    }
+
+    /// Parameters that can be set with `-p name=value`.
+    pub fn params(&self) -> &'static [Param] {
+        match self {
+            #[cfg(feature = "day06")]
+            Day::Day06 => day06::PARAMS,
+            #[cfg(feature = "day07")]
+            Day::Day07 => day07::PARAMS,
+            #[cfg(feature = "day09")]
+            Day::Day09 => day09::PARAMS,
+            #[cfg(feature = "day10")]
+            Day::Day10 => day10::PARAMS,
+            #[cfg(feature = "day11")]
+            Day::Day11 => day11::PARAMS,
+            _ => &[],
+        }
+    }
+
+    /// Names of the day's alternative implementations, the default first.
+    /// Empty if there's only one.
+    pub fn impls(&self) -> &'static [&'static str] {
+        match self {
+            #[cfg(feature = "day06")]
+            Day::Day06 => day06::IMPLS,
+            #[cfg(feature = "day08")]
+            Day::Day08 => day08::IMPLS,
+            _ => &[],
+        }
+    }
+
+    /// Generates a random input of roughly the given size, for days that
+    /// know how to.
+    pub fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        match self {
+            #[cfg(feature = "day03")]
+            Day::Day03 => Some(day03::generate(size, rng)),
+            #[cfg(feature = "day06")]
+            Day::Day06 => Some(day06::generate(size, rng)),
+            #[cfg(feature = "day08")]
+            Day::Day08 => Some(day08::generate(size, rng)),
+            #[cfg(feature = "day09")]
+            Day::Day09 => Some(day09::generate(size, rng)),
+            _ => None,
+        }
+    }
+
+    /// Identifies the current version of the solution, by hashing its
+    /// source along with the shared code it runs on, so that cached answers
+    /// are dropped whenever any of it changes.
+    pub fn version(&self) -> String {
+        let source = match self {
+            Day::Day01 => include_str!("day01.rs"),
+            Day::Day02 => include_str!("day02.rs"),
+            Day::Day03 => include_str!("day03.rs"),
+            Day::Day04 => include_str!("day04.rs"),
+            Day::Day05 => include_str!("day05.rs"),
+            Day::Day06 => include_str!("day06.rs"),
+            Day::Day07 => include_str!("day07.rs"),
+            Day::Day08 => include_str!("day08.rs"),
+            Day::Day09 => include_str!("day09.rs"),
+            Day::Day10 => include_str!("day10.rs"),
+            Day::Day11 => include_str!("day11.rs"),
+        };
+        let mut sources = vec![env!("CARGO_PKG_VERSION"), source];
+        sources.extend_from_slice(SHARED_SOURCES);
+        format!("{:016x}", cache::hash(sources.concat().as_bytes()))
+    }
+
+    pub fn input_path(&self) -> String {
+        format!("inputs/{}.txt", self)
+    }
+
+    pub fn source_path(&self) -> String {
+        format!("src/{}.rs", self)
+    }
+}
+
 impl Display for Day {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.to_possible_value().unwrap().get_name())
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, ValueEnum)]
 pub enum Part {
     Part1,
     Part2,
 }
 
+impl Part {
+    pub fn all() -> &'static [Part] {
+        Part::value_variants()
+    }
+}
+
 impl Display for Part {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.to_possible_value().unwrap().get_name())
     }
 }
+
+/// Where a solution's printed output goes.
+///
+/// Used with `write!` and `writeln!` like stdout, but without any
+/// `Result` to unwrap.
+pub enum Output {
+    Stdout,
+    Captured(String),
+}
+
+impl Output {
+    pub fn write_fmt(&mut self, args: fmt::Arguments) {
+        match self {
+            Output::Stdout => io::stdout().write_fmt(args).expect("Write to stdout"),
+            Output::Captured(buf) => buf.write_fmt(args).unwrap(),
+        }
+    }
+
+    /// Takes what's been captured so far, which is nothing for stdout.
+    pub fn take_captured(&mut self) -> String {
+        match self {
+            Output::Stdout => String::new(),
+            Output::Captured(buf) => std::mem::take(buf),
+        }
+    }
+}
+
+/// State handed to a solution for a single run.
+pub struct Context {
+    pub input: Input,
+    pub out: Output,
+    pub anim: Animator,
+    pub trace: Trace,
+    pub artifacts: Artifacts,
+    /// Which of the day's implementations to use, or `None` for the first.
+    pub impl_name: Option<String>,
+    pub params: Params,
+    /// When the solution finished parsing its input, see [`Context::parsed`].
+    pub parsed_at: Option<Instant>,
+}
+
+impl Context {
+    pub fn new(input: Input) -> Self {
+        Self {
+            input,
+            out: Output::Stdout,
+            anim: Animator::disabled(),
+            trace: Trace::disabled(),
+            artifacts: Artifacts::disabled(),
+            impl_name: None,
+            params: Params::default(),
+            parsed_at: None,
+        }
+    }
+
+    pub fn captured(input: Input) -> Self {
+        Self {
+            out: Output::Captured(String::new()),
+            ..Self::new(input)
+        }
+    }
+
+    /// Marks the end of parsing the input, so the runtime can be split into
+    /// parse and solve time.
+    pub fn parsed(&mut self) {
+        self.parsed_at = Some(Instant::now());
+    }
+}
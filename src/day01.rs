@@ -1,25 +1,49 @@
-use crate::day::Part;
-use std::fs;
-use std::io::{self, prelude::*};
+use crate::check::Problem;
+use crate::day::{Context, Part};
 
-pub fn run(part: Part, file_path: &str) {
+pub fn run(part: Part, ctx: &mut Context) -> Option<String> {
     match part {
-        Part::Part1 => part1(file_path),
-        Part::Part2 => part2(file_path),
+        Part::Part1 => Some(part1(ctx)),
+        Part::Part2 => Some(part2(ctx)),
     }
 }
 
-fn part1(file_path: &str) {
-    let file = fs::File::open(file_path).expect("Read the input file");
-    let reader = io::BufReader::new(file);
+pub fn check(input: &str) -> Vec<Problem> {
+    let mut problems = vec![];
+    let mut elves = 0;
+    let mut in_elf = false;
+    for (i, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            in_elf = false;
+            continue;
+        }
+        if !in_elf {
+            elves += 1;
+            in_elf = true;
+        }
+        if line.parse::<i32>().is_err() {
+            problems.push(Problem::at(i, format!("not a number: {:?}", line)));
+        }
+    }
+    if elves < 3 {
+        problems.push(Problem::new(format!(
+            "only {} elves, part2 needs at least 3",
+            elves
+        )));
+    }
+    problems
+}
 
+fn part1(ctx: &mut Context) -> String {
     let mut lines = 0;
     let mut sum = 0;
     let mut biggest_sum = 0;
 
-    for line in reader.lines().map(|l| l.unwrap()) {
+    let bytes = ctx.input.bytes();
+
+    for line in bytes.lines() {
         lines += 1;
-        if line == "" {
+        if line.is_empty() {
             if sum > biggest_sum {
                 biggest_sum = sum;
             }
@@ -29,22 +53,26 @@ fn part1(file_path: &str) {
         let value: i32 = line.parse().unwrap();
         sum += value;
     }
+    // The last elf isn't followed by an empty line
+    if sum > biggest_sum {
+        biggest_sum = sum;
+    }
 
-    println!("Lines: {}", lines);
-    println!("Biggest sum: {}", biggest_sum);
+    writeln!(ctx.out, "Lines: {}", lines);
+    writeln!(ctx.out, "Biggest sum: {}", biggest_sum);
+    biggest_sum.to_string()
 }
 
-fn part2(file_path: &str) {
-    let file = fs::File::open(file_path).expect("Read the input file");
-    let reader = io::BufReader::new(file);
-
+fn part2(ctx: &mut Context) -> String {
     let mut lines = 0;
     let mut sum = 0;
     let mut sums = vec![];
 
-    for line in reader.lines().map(|l| l.unwrap()) {
+    let bytes = ctx.input.bytes();
+
+    for line in bytes.lines() {
         lines += 1;
-        if line == "" {
+        if line.is_empty() {
             sums.push(sum);
             sum = 0;
             continue;
@@ -52,12 +80,33 @@ fn part2(file_path: &str) {
         let value: i32 = line.parse().unwrap();
         sum += value;
     }
+    // The last elf isn't followed by an empty line
+    sums.push(sum);
 
     sums.sort_by(|a, b| b.cmp(a));
 
-    println!("Lines: {}", lines);
-    println!("0: {}", sums[0]);
-    println!("1: {}", sums[1]);
-    println!("2: {}", sums[2]);
-    println!("Sum: {}", sums[0] + sums[1] + sums[2]);
+    writeln!(ctx.out, "Lines: {}", lines);
+    writeln!(ctx.out, "0: {}", sums[0]);
+    writeln!(ctx.out, "1: {}", sums[1]);
+    writeln!(ctx.out, "2: {}", sums[2]);
+    let top3_sum = sums[0] + sums[1] + sums[2];
+    writeln!(ctx.out, "Sum: {}", top3_sum);
+    top3_sum.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day::Context;
+    use crate::day01::{part1, part2};
+    use crate::input::Input;
+
+    #[test]
+    fn test_last_elf() {
+        // Without an empty line after the last elf, like the example
+        let input = "1\n\n2\n\n3\n\n10\n";
+        let mut ctx = Context::captured(Input::Text(input.to_string()));
+        assert_eq!(part1(&mut ctx), "10");
+        let mut ctx = Context::captured(Input::Text(input.to_string()));
+        assert_eq!(part2(&mut ctx), "15");
+    }
 }
@@ -1,14 +1,28 @@
-use crate::day::Part;
-use std::fs;
-use std::io::{self, prelude::*};
+use crate::check::Problem;
+use crate::day::{Context, Part};
 
-pub fn run(part: Part, file_path: &str) {
+pub fn run(part: Part, ctx: &mut Context) -> Option<String> {
     match part {
-        Part::Part1 => part1(file_path),
-        Part::Part2 => part2(file_path),
+        Part::Part1 => Some(part1(ctx)),
+        Part::Part2 => Some(part2(ctx)),
     }
 }
 
+pub fn check(input: &str) -> Vec<Problem> {
+    let mut problems = vec![];
+    for (i, line) in input.lines().enumerate() {
+        let chars: Vec<char> = line.chars().collect();
+        match chars.as_slice() {
+            ['A'..='C', ' ', 'X'..='Z'] => (),
+            _ => problems.push(Problem::at(
+                i,
+                format!("expected \"<A|B|C> <X|Y|Z>\", got {:?}", line),
+            )),
+        }
+    }
+    problems
+}
+
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 enum Move {
     Rock,
@@ -89,51 +103,49 @@ impl Outcome {
             (Outcome::Lose, Move::Rock) => Move::Scissors,
             (Outcome::Lose, Move::Paper) => Move::Rock,
             (Outcome::Lose, Move::Scissors) => Move::Paper,
-            (Outcome::Draw, _) => opponent.clone(),
+            (Outcome::Draw, _) => *opponent,
         }
     }
 }
 
-fn part1(file_path: &str) {
-    let file = fs::File::open(file_path).expect("Read the input file");
-    let reader = io::BufReader::new(file);
-
+fn part1(ctx: &mut Context) -> String {
     let mut sum = 0;
-    for line in reader.lines().map(|l| l.unwrap()) {
-        let (opponent, your) = parse_moves(&line);
+    let bytes = ctx.input.bytes();
+    for line in bytes.lines() {
+        let (opponent, your) = parse_moves(line);
 
         let outcome = your.battle(&opponent);
         let score = your.shape_score() + outcome.outcome_score();
         sum += score;
     }
 
-    println!("Total score: {}", sum);
+    writeln!(ctx.out, "Total score: {}", sum);
+    sum.to_string()
 }
 
 fn parse_moves(line: &str) -> (Move, Move) {
-    let opponent_letter = line.chars().nth(0).unwrap();
+    let opponent_letter = line.chars().next().unwrap();
     let your_letter = line.chars().nth(2).unwrap();
     (Move::parse(opponent_letter), Move::parse(your_letter))
 }
 
 fn parse_part2_codes(line: &str) -> (Move, Outcome) {
-    let opponent_letter = line.chars().nth(0).unwrap();
+    let opponent_letter = line.chars().next().unwrap();
     let outcome_letter = line.chars().nth(2).unwrap();
     (Move::parse(opponent_letter), Outcome::parse(outcome_letter))
 }
 
-fn part2(file_path: &str) {
-    let file = fs::File::open(file_path).expect("Read the input file");
-    let reader = io::BufReader::new(file);
-
+fn part2(ctx: &mut Context) -> String {
     let mut sum = 0;
-    for line in reader.lines().map(|l| l.unwrap()) {
-        let (opponent, outcome) = parse_part2_codes(&line);
+    let bytes = ctx.input.bytes();
+    for line in bytes.lines() {
+        let (opponent, outcome) = parse_part2_codes(line);
 
         let your = outcome.calc_uour_move(&opponent);
         let score = your.shape_score() + outcome.outcome_score();
         sum += score;
     }
 
-    println!("Total score: {}", sum);
+    writeln!(ctx.out, "Total score: {}", sum);
+    sum.to_string()
 }
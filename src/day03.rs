@@ -1,34 +1,133 @@
-use crate::day::Part;
+use crate::check::Problem;
+use crate::day::{Context, Part};
+use crate::generate::Rng;
+use crate::trace::explain;
 use std::collections::HashSet;
-use std::fs;
-use std::io::{self, prelude::*};
 
-pub fn run(part: Part, file_path: &str) {
+pub fn run(part: Part, ctx: &mut Context) -> Option<String> {
     match part {
-        Part::Part1 => part1(file_path),
-        Part::Part2 => part2(file_path),
+        Part::Part1 => Some(part1(ctx)),
+        Part::Part2 => Some(part2(ctx)),
     }
 }
 
-fn part1(file_path: &str) {
-    let file = fs::File::open(file_path).expect("Read the input file");
-    let reader = io::BufReader::new(file);
+pub fn check(input: &str) -> Vec<Problem> {
+    let mut problems = vec![];
+    let lines: Vec<&str> = input.lines().collect();
+    for (i, line) in lines.iter().enumerate() {
+        if let Some(c) = line.chars().find(|c| !c.is_ascii_alphabetic()) {
+            problems.push(Problem::at(i, format!("unexpected item {:?}", c)));
+        } else if line.is_empty() {
+            problems.push(Problem::at(i, "empty rucksack"));
+        } else if line.len() % 2 != 0 {
+            problems.push(Problem::at(
+                i,
+                format!("odd number of items ({}), can't split in two", line.len()),
+            ));
+        } else {
+            let (a, b) = split_compartments(line);
+            if find_common(a, b).is_none() {
+                problems.push(Problem::at(i, "compartments have no item in common"));
+            }
+        }
+    }
+
+    if !lines.len().is_multiple_of(3) {
+        problems.push(Problem::new(format!(
+            "{} rucksacks is not a multiple of 3, part2 needs groups of 3",
+            lines.len()
+        )));
+    } else {
+        for (group, chunk) in lines.chunks(3).enumerate() {
+            if find_common_3(chunk[0], chunk[1], chunk[2]).is_none() {
+                problems.push(Problem::at(group * 3, "group of 3 has no badge in common"));
+            }
+        }
+    }
+    problems
+}
+
+/// Generates `size` rucksacks, rounded up to whole groups of 3.
+///
+/// Each rucksack's compartments share exactly one item, and each group
+/// exactly one badge, so that every solution finds the same answer.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let letters: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+    let mut input = String::new();
+    for _ in 0..size.div_ceil(3) {
+        let badge = letters[rng.below(letters.len() as u64) as usize];
+        let mut group: Vec<String> = vec![];
+        for _ in 0..3 {
+            // The last rucksack can't have anything the other two share
+            let allowed: Vec<char> = letters
+                .iter()
+                .copied()
+                .filter(|&c| c != badge)
+                .filter(|&c| !(group.len() == 2 && group[0].contains(c) && group[1].contains(c)))
+                .collect();
+            group.push(generate_rucksack(badge, &allowed, rng));
+        }
+        for rucksack in group {
+            input.push_str(&rucksack);
+            input.push('\n');
+        }
+    }
+    input
+}
+
+/// A rucksack with the badge in the first compartment, and one item from
+/// `allowed` in both compartments.
+fn generate_rucksack(badge: char, allowed: &[char], rng: &mut Rng) -> String {
+    let common = allowed[rng.below(allowed.len() as u64) as usize];
+    // Split the rest between the compartments so they share nothing else
+    let (mut first, mut second) = (vec![], vec![]);
+    for &c in allowed.iter().filter(|&&c| c != common) {
+        if rng.below(2) == 0 {
+            first.push(c);
+        } else {
+            second.push(c);
+        }
+    }
+    if first.is_empty() {
+        first.push(badge);
+    }
+    if second.is_empty() {
+        second.push(common);
+    }
+    let mut random = |from: &[char], n: usize| -> String {
+        (0..n)
+            .map(|_| from[rng.below(from.len() as u64) as usize])
+            .collect()
+    };
+    format!(
+        "{}{}{}{}{}",
+        badge,
+        random(&first, 10),
+        common,
+        common,
+        random(&second, 11)
+    )
+}
 
+fn part1(ctx: &mut Context) -> String {
     let mut sum = 0;
-    for line in reader.lines().map(|l| l.unwrap()) {
-        let (a, b) = split_compartments(&line);
+    let bytes = ctx.input.bytes();
+    for line in bytes.lines() {
+        let (a, b) = split_compartments(line);
         let common = find_common(a, b).expect("No common letter :(");
         let score = letter_score(common);
+        explain!(ctx.trace, "{} | {}: {} has priority {}", a, b, common, score);
         sum += score;
     }
 
-    println!("Prio score sum: {}", sum);
+    writeln!(ctx.out, "Prio score sum: {}", sum);
+    sum.to_string()
 }
 
 fn split_compartments(s: &str) -> (&str, &str) {
     let a = &s[0..s.len()/2];
     let b = &s[s.len()/2..];
-    return (a, b);
+    (a, b)
 }
 
 fn find_common(a: &str, b: &str) -> Option<char> {
@@ -39,22 +138,20 @@ fn find_common(a: &str, b: &str) -> Option<char> {
             }
         }
     }
-    return None;
+    None
 }
 
 fn letter_score(c: char) -> u32 {
     match c {
-        c if c >= 'a' && c <= 'z' => c as u32 - 'a' as u32 + 1,
-        c if c >= 'A' && c <= 'Z' => c as u32 - 'A' as u32 + 27,
+        c if c.is_ascii_lowercase() => c as u32 - 'a' as u32 + 1,
+        c if c.is_ascii_uppercase() => c as u32 - 'A' as u32 + 27,
         _ => panic!("Bad letter"),
     }
 }
 
-fn part2(file_path: &str) {
-    let file = fs::File::open(file_path).expect("Read the input file");
-    let reader = io::BufReader::new(file);
-
-    let mut lines_iter = reader.lines().map(|l| l.unwrap());
+fn part2(ctx: &mut Context) -> String {
+    let bytes = ctx.input.bytes();
+    let mut lines_iter = bytes.lines();
     let mut sum = 0;
     let mut count = 0;
     loop {
@@ -63,9 +160,16 @@ fn part2(file_path: &str) {
             Some(a) => {
                 let b = lines_iter.next().unwrap();
                 let c = lines_iter.next().unwrap();
-                match find_common_3(&a,&b,&c) {
-                    Some(c) => {
-                        sum += letter_score(c);
+                match find_common_3(a, b, c) {
+                    Some(badge) => {
+                        explain!(
+                            ctx.trace,
+                            "Group {}: badge {} has priority {}",
+                            count + 1,
+                            badge,
+                            letter_score(badge)
+                        );
+                        sum += letter_score(badge);
                         count += 1;
                     },
                     None => {
@@ -76,8 +180,9 @@ fn part2(file_path: &str) {
         }
     }
 
-    println!("Groups: {}", count);
-    println!("Grouped badges prio score sum: {}", sum);
+    writeln!(ctx.out, "Groups: {}", count);
+    writeln!(ctx.out, "Grouped badges prio score sum: {}", sum);
+    sum.to_string()
 }
 
 fn find_common_3(a: &str, b: &str, c: &str) -> Option<char> {
@@ -89,16 +194,17 @@ fn find_common_3(a: &str, b: &str, c: &str) -> Option<char> {
         .cloned()
         .collect::<HashSet<char>>();
 
-    let intersection = a_and_b .intersection(&c_set);
-    for c in intersection {
-        return Some(c.clone());
-    }
-    None
+    let mut intersection = a_and_b.intersection(&c_set);
+    intersection.next().copied()
 }
 
 #[cfg(test)]
 mod tests {
-    use super::letter_score;
+    use std::collections::HashSet;
+
+    use super::{check, find_common, generate, letter_score, split_compartments};
+    use crate::check::Problem;
+    use crate::generate::Rng;
 
     #[test]
     fn test_letter_score() {
@@ -107,4 +213,37 @@ mod tests {
         assert_eq!(letter_score('A'), 27);
         assert_eq!(letter_score('Z'), 52);
     }
+
+    #[test]
+    fn test_check() {
+        assert_eq!(check("abca\nAbcb\nxbxb\n"), vec![]);
+        assert_eq!(
+            check("abca\nAbc\nxbxb\nab\n"),
+            vec![
+                Problem::at(1, "odd number of items (3), can't split in two"),
+                Problem::at(3, "compartments have no item in common"),
+                Problem::new("4 rucksacks is not a multiple of 3, part2 needs groups of 3"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(300, &mut Rng::new(3));
+        assert_eq!(check(&input), vec![]);
+        let lines: Vec<&str> = input.lines().collect();
+        for line in &lines {
+            let (a, b) = split_compartments(line);
+            let common: HashSet<char> = a.chars().filter(|&c| b.contains(c)).collect();
+            assert_eq!(common.len(), 1, "{}", line);
+            assert_eq!(find_common(a, b), common.into_iter().next());
+        }
+        for group in lines.chunks(3) {
+            let badges = group[0]
+                .chars()
+                .filter(|&c| group[1].contains(c) && group[2].contains(c))
+                .collect::<HashSet<char>>();
+            assert_eq!(badges.len(), 1, "{:?}", group);
+        }
+    }
 }
@@ -1,34 +1,58 @@
-use crate::day::Part;
-use std::fs;
-use std::io::{self, prelude::*};
+use crate::check::Problem;
+use crate::day::{Context, Part};
+use crate::trace::explain;
 
-pub fn run(part: Part, file_path: &str) {
+pub fn run(part: Part, ctx: &mut Context) -> Option<String> {
     match part {
-        Part::Part1 => part1(file_path),
-        Part::Part2 => part2(file_path),
+        Part::Part1 => Some(part1(ctx)),
+        Part::Part2 => Some(part2(ctx)),
     }
 }
 
-fn part1(file_path: &str) {
-    let file = fs::File::open(file_path).expect("Read the input file");
-    let reader = io::BufReader::new(file);
+pub fn check(input: &str) -> Vec<Problem> {
+    let mut problems = vec![];
+    for (i, line) in input.lines().enumerate() {
+        match try_parse_2_ranges(line) {
+            Some((a, b)) => {
+                for range in [a, b] {
+                    if range.from > range.to {
+                        problems.push(Problem::at(
+                            i,
+                            format!("range {}-{} is backwards", range.from, range.to),
+                        ));
+                    }
+                }
+            }
+            None => problems.push(Problem::at(
+                i,
+                format!("expected \"<from>-<to>,<from>-<to>\", got {:?}", line),
+            )),
+        }
+    }
+    problems
+}
 
+fn part1(ctx: &mut Context) -> String {
     let mut lines_count = 0;
     let mut contain_count = 0;
 
-    for line in reader.lines().map(|l| l.unwrap()) {
+    let bytes = ctx.input.bytes();
+
+    for line in bytes.lines() {
         lines_count += 1;
 
-        let (a, b) = try_parse_2_ranges(&line).expect("Failed to parse line");
+        let (a, b) = try_parse_2_ranges(line).expect("Failed to parse line");
         let any_contains = a.contains(&b) || b.contains(&a);
 
         if any_contains {
+            explain!(ctx.trace, "Line {}: {} contains the other", lines_count, line);
             contain_count += 1;
         }
     }
 
-    println!("Lines count: {}", lines_count);
-    println!("Count where either contains the other: {}", contain_count);
+    writeln!(ctx.out, "Lines count: {}", lines_count);
+    writeln!(ctx.out, "Count where either contains the other: {}", contain_count);
+    contain_count.to_string()
 }
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
@@ -44,21 +68,21 @@ impl Range {
 
     fn try_parse(s: &str) -> Option<Range> {
         let (from_s, to_s) = s.split_once('-')?;
-        let from_i = from_s.parse::<i32>().expect("Failed to parse 'from'");
-        let to_i = to_s.parse::<i32>().expect("Failed to parse 'to'");
-        return Some(Self::new(from_i, to_i));
+        let from_i = from_s.parse::<i32>().ok()?;
+        let to_i = to_s.parse::<i32>().ok()?;
+        Some(Self::new(from_i, to_i))
     }
 
     fn contains(&self, other: &Range) -> bool {
-        return self.from <= other.from && self.to >= other.to;
+        self.from <= other.from && self.to >= other.to
     }
 
     fn contains_point(&self, point: i32) -> bool {
-        return point >= self.from && point <= self.to;
+        point >= self.from && point <= self.to
     }
 
     fn contains_border(&self, other: &Range) -> bool {
-        return self.contains_point(other.from) || self.contains_point(other.to);
+        self.contains_point(other.from) || self.contains_point(other.to)
     }
 }
 
@@ -66,29 +90,30 @@ fn try_parse_2_ranges(s: &str) -> Option<(Range, Range)> {
     let (first, second) = s.split_once(',')?;
     let first_range = Range::try_parse(first)?;
     let second_range = Range::try_parse(second)?;
-    return Some((first_range, second_range));
+    Some((first_range, second_range))
 }
 
-fn part2(file_path: &str) {
-    let file = fs::File::open(file_path).expect("Read the input file");
-    let reader = io::BufReader::new(file);
-
+fn part2(ctx: &mut Context) -> String {
     let mut lines_count = 0;
     let mut overlaps_count = 0;
 
-    for line in reader.lines().map(|l| l.unwrap()) {
+    let bytes = ctx.input.bytes();
+
+    for line in bytes.lines() {
         lines_count += 1;
 
-        let (a, b) = try_parse_2_ranges(&line).expect("Failed to parse line");
+        let (a, b) = try_parse_2_ranges(line).expect("Failed to parse line");
         let overlaps = a.contains_border(&b) || b.contains_border(&a);
 
         if overlaps {
+            explain!(ctx.trace, "Line {}: {} overlap", lines_count, line);
             overlaps_count += 1;
         }
     }
 
-    println!("Lines count: {}", lines_count);
-    println!("Count where they overlap: {}", overlaps_count);
+    writeln!(ctx.out, "Lines count: {}", lines_count);
+    writeln!(ctx.out, "Count where they overlap: {}", overlaps_count);
+    overlaps_count.to_string()
 }
 
 #[cfg(test)]
@@ -1,15 +1,19 @@
-use crate::day::Part;
-use std::fs;
-use std::io::{self, prelude::*};
+use crate::anim::Frame;
+use crate::artifact;
+use crate::check::Problem;
+use crate::day::{Context, Part};
+use crate::json;
+use crate::trace::explain;
+use std::fmt;
 
-pub fn run(part: Part, file_path: &str) {
+pub fn run(part: Part, ctx: &mut Context) -> Option<String> {
     match part {
-        Part::Part1 => part1(file_path),
-        Part::Part2 => part2(file_path),
+        Part::Part1 => Some(part1(ctx)),
+        Part::Part2 => Some(part2(ctx)),
     }
 }
 
-fn get_start_stacks() -> [Box<Vec<char>>; 9] {
+fn get_start_stacks() -> [Vec<char>; 9] {
     // I'm not parsing this thing automatically, nuh uh:
     // [N]             [R]             [C]
     // [T] [J]         [S] [J]         [N]
@@ -21,28 +25,112 @@ fn get_start_stacks() -> [Box<Vec<char>>; 9] {
     // [R] [H] [Z] [M] [T] [M] [T] [Q] [W]
     //  1   2   3   4   5   6   7   8   9
     [
-        Box::new(vec!['R', 'G', 'H', 'Q', 'S', 'B', 'T', 'N']),
-        Box::new(vec!['H', 'S', 'F', 'D', 'P', 'Z', 'J']),
-        Box::new(vec!['Z', 'H', 'V']),
-        Box::new(vec!['M', 'Z', 'J', 'F', 'G', 'H']),
-        Box::new(vec!['T', 'Z', 'C', 'D', 'L', 'M', 'S', 'R']),
-        Box::new(vec!['M', 'T', 'W', 'V', 'H', 'Z', 'J']),
-        Box::new(vec!['T', 'F', 'P', 'L', 'Z']),
-        Box::new(vec!['Q', 'V', 'W', 'S']),
-        Box::new(vec!['W', 'H', 'L', 'M', 'T', 'D', 'N', 'C']),
+        vec!['R', 'G', 'H', 'Q', 'S', 'B', 'T', 'N'],
+        vec!['H', 'S', 'F', 'D', 'P', 'Z', 'J'],
+        vec!['Z', 'H', 'V'],
+        vec!['M', 'Z', 'J', 'F', 'G', 'H'],
+        vec!['T', 'Z', 'C', 'D', 'L', 'M', 'S', 'R'],
+        vec!['M', 'T', 'W', 'V', 'H', 'Z', 'J'],
+        vec!['T', 'F', 'P', 'L', 'Z'],
+        vec!['Q', 'V', 'W', 'S'],
+        vec!['W', 'H', 'L', 'M', 'T', 'D', 'N', 'C'],
     ]
 }
 
-fn part1(file_path: &str) {
-    let file = fs::File::open(file_path).expect("Read the input file");
-    let reader = io::BufReader::new(file);
+pub fn check(input: &str) -> Vec<Problem> {
+    let mut problems = vec![];
+    let mut lines_iter = input.lines().enumerate();
 
-    let mut lines_iter = reader.lines().map(|l| l.unwrap()).into_iter();
+    let mut header = vec![];
+    let mut separated = false;
+    for (_, line) in lines_iter.by_ref() {
+        if line.is_empty() {
+            separated = true;
+            break;
+        }
+        header.push(line);
+    }
+
+    let crate_row = regex::Regex::new(r"^( {3}|\[[A-Z]\])( ( {3}|\[[A-Z]\]))* *$").unwrap();
+    let numbers_row = regex::Regex::new(r"^ *\d+( +\d+)* *$").unwrap();
+    for (i, row) in header.iter().enumerate() {
+        let is_last = i + 1 == header.len();
+        if !(is_last && numbers_row.is_match(row) || !is_last && crate_row.is_match(row)) {
+            problems.push(Problem::at(i, format!("not part of a crate drawing: {:?}", row)));
+        }
+    }
+
+    if !separated {
+        problems.push(Problem::new(
+            "no empty line between the crate drawing and the moves",
+        ));
+        return problems;
+    }
+
+    // The solution uses hardcoded stacks, so the drawing must match them
+    let mut drawn_stacks: [Vec<char>; 9] = Default::default();
+    for row in header.iter().rev().skip(1) {
+        for (i, stack) in drawn_stacks.iter_mut().enumerate() {
+            match row.chars().nth(i * 4 + 1) {
+                Some(c) if c.is_ascii_uppercase() => stack.push(c),
+                _ => (),
+            }
+        }
+    }
+    if drawn_stacks != get_start_stacks() {
+        problems.push(Problem::new(
+            "crate drawing doesn't match the hardcoded start stacks",
+        ));
+    }
+
+    let move_regex = regex::Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
+    let mut sizes = get_start_stacks().map(|stack| stack.len());
+    for (i, line) in lines_iter {
+        let numbers: Option<Vec<usize>> = move_regex.captures(line).and_then(|captures| {
+            (1..=3)
+                .map(|n| captures.get(n).unwrap().as_str().parse().ok())
+                .collect()
+        });
+        let (count, from, to) = match numbers.as_deref() {
+            Some(&[count, from, to]) => (count, from, to),
+            _ => {
+                problems.push(Problem::at(
+                    i,
+                    format!("expected \"move <n> from <n> to <n>\", got {:?}", line),
+                ));
+                continue;
+            }
+        };
+        if !(1..=9).contains(&from) || !(1..=9).contains(&to) {
+            problems.push(Problem::at(i, "stacks are numbered 1 to 9"));
+            continue;
+        }
+        if sizes[from - 1] < count {
+            problems.push(Problem::at(
+                i,
+                format!(
+                    "moves {} crates from stack {} which only has {}",
+                    count,
+                    from,
+                    sizes[from - 1]
+                ),
+            ));
+        }
+        let moved = count.min(sizes[from - 1]);
+        sizes[from - 1] -= moved;
+        sizes[to - 1] += moved;
+    }
+    problems
+}
+
+fn part1(ctx: &mut Context) -> String {
+    let bytes = ctx.input.bytes();
+    let mut lines_iter = bytes.lines();
 
     // skip the header
     loop {
         match lines_iter.next() {
-            Some(a) if a == "" => break,
+            Some("") => break,
             None => break,
             _ => (),
         }
@@ -55,7 +143,7 @@ fn part1(file_path: &str) {
     let mut lines_count = 0;
     for line in lines_iter {
         lines_count += 1;
-        let captures = move_regex.captures(&line).expect("Didn't match");
+        let captures = move_regex.captures(line).expect("Didn't match");
         let count_match = captures.get(1).expect("No count");
         let from_match = captures.get(2).expect("No from");
         let to_match = captures.get(3).expect("No to");
@@ -67,41 +155,83 @@ fn part1(file_path: &str) {
         let from_index = from - 1;
         let to_index = to - 1;
 
+        let mut moved = String::new();
         for _ in 1..=count {
             let value = pop_from_index(&mut stacks, from_index);
             push_to_index(&mut stacks, to_index, value);
+            moved.push(value);
         }
+        explain!(ctx.trace, "Move {} from {} to {}: {}", count, from, to, moved);
+        ctx.anim.step(&StacksFrame(&stacks));
     }
+    ctx.anim.finish(&StacksFrame(&stacks));
+    emit_stacks(ctx, &stacks);
 
-    println!("Lines count: {}", lines_count);
-    print!("Code: ");
-    for vec in stacks {
-        let value = vec.last().unwrap();
-        print!("{}", value);
-    }
-    println!();
+    writeln!(ctx.out, "Lines count: {}", lines_count);
+    let code = top_of_stacks(&stacks);
+    writeln!(ctx.out, "Code: {}", code);
+    code
 }
 
-fn pop_from_index(stacks: &mut [Box<Vec<char>>; 9], index: usize) -> char {
+fn top_of_stacks(stacks: &[Vec<char>; 9]) -> String {
+    stacks.iter().map(|vec| vec.last().unwrap()).collect()
+}
+
+fn pop_from_index(stacks: &mut [Vec<char>; 9], index: usize) -> char {
     let from_vec = stacks.get_mut(index).unwrap();
-    return from_vec.pop().unwrap();
+    from_vec.pop().unwrap()
 }
 
-fn push_to_index(stacks: &mut [Box<Vec<char>>; 9], index: usize, c: char) {
+fn push_to_index(stacks: &mut [Vec<char>; 9], index: usize, c: char) {
     let from_vec = stacks.get_mut(index).unwrap();
     from_vec.push(c);
 }
 
-fn part2(file_path: &str) {
-    let file = fs::File::open(file_path).expect("Read the input file");
-    let reader = io::BufReader::new(file);
+/// The final stacks, drawn and as JSON from bottom to top.
+fn emit_stacks(ctx: &mut Context, stacks: &[Vec<char>; 9]) {
+    ctx.artifacts.emit("stacks", artifact::Kind::Text, || {
+        let mut drawn = String::new();
+        StacksFrame(stacks).draw(&mut drawn).expect("Draw stacks");
+        drawn
+    });
+    ctx.artifacts.emit("stacks", artifact::Kind::Json, || {
+        let stacks: Vec<String> = stacks
+            .iter()
+            .map(|stack| json::string(&stack.iter().collect::<String>()))
+            .collect();
+        format!("{{\"stacks\":[{}]}}\n", stacks.join(","))
+    });
+}
+
+struct StacksFrame<'a>(&'a [Vec<char>; 9]);
 
-    let mut lines_iter = reader.lines().map(|l| l.unwrap()).into_iter();
+impl Frame for StacksFrame<'_> {
+    fn draw(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        let height = self.0.iter().map(|stack| stack.len()).max().unwrap_or(0);
+        for row in (0..height).rev() {
+            for stack in self.0 {
+                match stack.get(row) {
+                    Some(c) => write!(f, "[{}] ", c)?,
+                    None => write!(f, "    ")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        for i in 1..=self.0.len() {
+            write!(f, " {}  ", i)?;
+        }
+        writeln!(f)
+    }
+}
+
+fn part2(ctx: &mut Context) -> String {
+    let bytes = ctx.input.bytes();
+    let mut lines_iter = bytes.lines();
 
     // skip the header
     loop {
         match lines_iter.next() {
-            Some(a) if a == "" => break,
+            Some("") => break,
             None => break,
             _ => (),
         }
@@ -114,7 +244,7 @@ fn part2(file_path: &str) {
     let mut lines_count = 0;
     for line in lines_iter {
         lines_count += 1;
-        let captures = move_regex.captures(&line).expect("Didn't match");
+        let captures = move_regex.captures(line).expect("Didn't match");
         let count_match = captures.get(1).expect("No count");
         let from_match = captures.get(2).expect("No from");
         let to_match = captures.get(3).expect("No to");
@@ -131,16 +261,18 @@ fn part2(file_path: &str) {
             let value = pop_from_index(&mut stacks, from_index);
             vec.insert(0, value);
         }
+        let moved: String = vec.iter().collect();
         for value in vec {
             push_to_index(&mut stacks, to_index, value);
         }
+        explain!(ctx.trace, "Move {} from {} to {}: {}", count, from, to, moved);
+        ctx.anim.step(&StacksFrame(&stacks));
     }
+    ctx.anim.finish(&StacksFrame(&stacks));
+    emit_stacks(ctx, &stacks);
 
-    println!("Lines count: {}", lines_count);
-    print!("Code: ");
-    for vec in stacks {
-        let value = vec.last().unwrap();
-        print!("{}", value);
-    }
-    println!();
+    writeln!(ctx.out, "Lines count: {}", lines_count);
+    let code = top_of_stacks(&stacks);
+    writeln!(ctx.out, "Code: {}", code);
+    code
 }
@@ -1,24 +1,99 @@
-use crate::day::Part;
-use std::fs;
+use crate::check::Problem;
+use crate::day::{Context, Part};
+use crate::generate::Rng;
+use crate::params::{Kind, Param, Params};
 
-pub fn run(part: Part, file_path: &str) {
+pub const IMPLS: &[&str] = &["naive", "bitmask"];
+
+pub const PARAMS: &[Param] = &[
+    Param {
+        name: "packet",
+        kind: Kind::Int,
+        default: "4",
+        min: 1,
+        max: 128,
+        help: "Length of the start-of-packet marker",
+    },
+    Param {
+        name: "message",
+        kind: Kind::Int,
+        default: "14",
+        min: 1,
+        max: 128,
+        help: "Length of the start-of-message marker",
+    },
+];
+
+type Finder = fn(data: &str, n: usize) -> Option<usize>;
+
+pub fn run(part: Part, ctx: &mut Context) -> Option<String> {
     match part {
-        Part::Part1 => part1(file_path),
-        Part::Part2 => part2(file_path),
+        Part::Part1 => Some(part1(ctx)),
+        Part::Part2 => Some(part2(ctx)),
     }
 }
 
-fn part1(file_path: &str) {
-    let data = fs::read_to_string(file_path).expect("Read the input file");
+pub fn check(input: &str) -> Vec<Problem> {
+    let mut problems = vec![];
+    let lines: Vec<&str> = input.lines().collect();
+    if lines.len() != 1 {
+        problems.push(Problem::new(format!(
+            "expected a single line, got {}",
+            lines.len()
+        )));
+    }
+    for (i, line) in lines.iter().enumerate() {
+        if let Some((column, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+            problems.push(Problem::at(
+                i,
+                format!("unexpected character {:?} at column {}", c, column + 1),
+            ));
+        }
+    }
+    if find_first_start_of_packet(input, find_where_ends_with_n_distinct).is_none() {
+        problems.push(Problem::new("no start-of-packet marker"));
+    } else if find_first_start_of_message(input, find_where_ends_with_n_distinct).is_none() {
+        problems.push(Problem::new("no start-of-message marker"));
+    }
+    problems
+}
 
-    println!(
-        "Start of packet: {}",
-        find_first_start_of_packet(&data).unwrap()
-    );
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    // Only 3 letters, so that the markers only show up in the alphabet at
+    // the end and solutions have to go through all of it
+    let mut data: String = (0..size)
+        .map(|_| (b'a' + rng.below(3) as u8) as char)
+        .collect();
+    data.push_str("abcdefghijklmnopqrstuvwxyz\n");
+    data
 }
 
-fn find_first_start_of_packet(data: &str) -> Option<usize> {
-    find_where_ends_with_n_distinct(data, 4)
+fn finder(ctx: &Context) -> Finder {
+    match ctx.impl_name.as_deref() {
+        Some("bitmask") => find_where_ends_with_n_distinct_bitmask,
+        _ => find_where_ends_with_n_distinct,
+    }
+}
+
+fn part1(ctx: &mut Context) -> String {
+    let bytes = ctx.input.bytes();
+    ctx.parsed();
+
+    let start = finder(ctx)(bytes.as_str(), ctx.params.int("packet") as usize)
+        .expect("No start-of-packet marker in the input");
+    writeln!(ctx.out, "Start of packet: {}", start);
+    start.to_string()
+}
+
+/// A marker length from the puzzle, which is the parameter's default.
+fn default_len(name: &str) -> usize {
+    let mut params = Params::default();
+    params.fill_defaults(PARAMS);
+    params.int(name) as usize
+}
+
+fn find_first_start_of_packet(data: &str, find: Finder) -> Option<usize> {
+    find(data, default_len("packet"))
 }
 
 fn find_where_ends_with_n_distinct(data: &str, n: usize) -> Option<usize> {
@@ -32,14 +107,13 @@ fn find_where_ends_with_n_distinct(data: &str, n: usize) -> Option<usize> {
     None
 }
 
-fn last_n_are_distinct(vec: &Vec<char>, n: usize) -> bool {
+fn last_n_are_distinct(vec: &[char], n: usize) -> bool {
     let len = vec.len();
     if len < n {
         return false;
     }
     let mut unique: Vec<char> = vec![];
-    for i in len - n..len {
-        let v = vec[i];
+    for &v in &vec[len - n..] {
         if unique.contains(&v) {
             return false;
         }
@@ -48,70 +122,114 @@ fn last_n_are_distinct(vec: &Vec<char>, n: usize) -> bool {
     true
 }
 
-fn part2(file_path: &str) {
-    let data = fs::read_to_string(file_path).expect("Read the input file");
+/// Same as [`find_where_ends_with_n_distinct`], but keeps a rolling bitmask
+/// of the window instead of rescanning it. Each character toggles its bit
+/// when entering and leaving the window, so any duplicate clears a bit and
+/// only `n` distinct characters leave `n` bits set. Only handles ASCII.
+fn find_where_ends_with_n_distinct_bitmask(data: &str, n: usize) -> Option<usize> {
+    let bytes = data.as_bytes();
+    let mut mask: u128 = 0;
+    for (i, &b) in bytes.iter().enumerate() {
+        mask ^= 1 << (b & 0x7f);
+        if i >= n {
+            mask ^= 1 << (bytes[i - n] & 0x7f);
+        }
+        if mask.count_ones() as usize == n {
+            return Some(i + 1);
+        }
+    }
+    None
+}
+
+fn part2(ctx: &mut Context) -> String {
+    let bytes = ctx.input.bytes();
+    ctx.parsed();
 
-    println!(
-        "Start of message: {}",
-        find_first_start_of_message(&data).unwrap()
-    );
+    let start = finder(ctx)(bytes.as_str(), ctx.params.int("message") as usize)
+        .expect("No start-of-message marker in the input");
+    writeln!(ctx.out, "Start of message: {}", start);
+    start.to_string()
 }
 
-fn find_first_start_of_message(data: &str) -> Option<usize> {
-    find_where_ends_with_n_distinct(data, 14)
+fn find_first_start_of_message(data: &str, find: Finder) -> Option<usize> {
+    find(data, default_len("message"))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day06::{find_first_start_of_message, find_first_start_of_packet};
+    use crate::day06::{
+        find_first_start_of_message, find_first_start_of_packet, find_where_ends_with_n_distinct,
+        find_where_ends_with_n_distinct_bitmask, Finder,
+    };
+
+    const FINDERS: [Finder; 2] = [
+        find_where_ends_with_n_distinct,
+        find_where_ends_with_n_distinct_bitmask,
+    ];
 
     #[test]
     fn test_find_first_start_of_packet() {
-        assert_eq!(find_first_start_of_packet(""), None);
-        assert_eq!(
-            find_first_start_of_packet("mjqjpqmgbljsphdztnvjfqwrcgsmlb"),
-            Some(7)
-        );
-        assert_eq!(
-            find_first_start_of_packet("bvwbjplbgvbhsrlpgdmjqwftvncz"),
-            Some(5)
-        );
-        assert_eq!(
-            find_first_start_of_packet("nppdvjthqldpwncqszvftbrmjlhg"),
-            Some(6)
-        );
-        assert_eq!(
-            find_first_start_of_packet("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"),
-            Some(10)
-        );
-        assert_eq!(
-            find_first_start_of_packet("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"),
-            Some(11)
-        );
+        for find in FINDERS {
+            assert_eq!(find_first_start_of_packet("", find), None);
+            assert_eq!(
+                find_first_start_of_packet("mjqjpqmgbljsphdztnvjfqwrcgsmlb", find),
+                Some(7)
+            );
+            assert_eq!(
+                find_first_start_of_packet("bvwbjplbgvbhsrlpgdmjqwftvncz", find),
+                Some(5)
+            );
+            assert_eq!(
+                find_first_start_of_packet("nppdvjthqldpwncqszvftbrmjlhg", find),
+                Some(6)
+            );
+            assert_eq!(
+                find_first_start_of_packet("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", find),
+                Some(10)
+            );
+            assert_eq!(
+                find_first_start_of_packet("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", find),
+                Some(11)
+            );
+        }
     }
 
     #[test]
     fn test_find_first_start_of_message() {
-        assert_eq!(find_first_start_of_message(""), None);
-        assert_eq!(
-            find_first_start_of_message("mjqjpqmgbljsphdztnvjfqwrcgsmlb"),
-            Some(19)
-        );
-        assert_eq!(
-            find_first_start_of_message("bvwbjplbgvbhsrlpgdmjqwftvncz"),
-            Some(23)
-        );
-        assert_eq!(
-            find_first_start_of_message("nppdvjthqldpwncqszvftbrmjlhg"),
-            Some(23)
-        );
-        assert_eq!(
-            find_first_start_of_message("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"),
-            Some(29)
-        );
-        assert_eq!(
-            find_first_start_of_message("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"),
-            Some(26)
-        );
+        for find in FINDERS {
+            assert_eq!(find_first_start_of_message("", find), None);
+            assert_eq!(
+                find_first_start_of_message("mjqjpqmgbljsphdztnvjfqwrcgsmlb", find),
+                Some(19)
+            );
+            assert_eq!(
+                find_first_start_of_message("bvwbjplbgvbhsrlpgdmjqwftvncz", find),
+                Some(23)
+            );
+            assert_eq!(
+                find_first_start_of_message("nppdvjthqldpwncqszvftbrmjlhg", find),
+                Some(23)
+            );
+            assert_eq!(
+                find_first_start_of_message("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", find),
+                Some(29)
+            );
+            assert_eq!(
+                find_first_start_of_message("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", find),
+                Some(26)
+            );
+        }
+    }
+
+    #[test]
+    fn test_find_other_lengths() {
+        for find in FINDERS {
+            assert_eq!(find("", 1), None);
+            assert_eq!(find("aab", 1), Some(1));
+            assert_eq!(find("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 2), Some(2));
+            assert_eq!(find("aabc", 3), Some(4));
+            assert_eq!(find("abcdefghijklmnopqrstuvwxyz", 26), Some(26));
+            assert_eq!(find("abcdefghijklmnopqrstuvwxyy", 26), None);
+        }
     }
 }
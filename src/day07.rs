@@ -1,13 +1,85 @@
-use crate::day::Part;
-use std::fs;
-use std::io::{self, prelude::*};
+use crate::artifact;
+use crate::check::Problem;
+use crate::day::{Context, Part};
+use crate::params::{Kind, Param};
+use crate::trace::{explain, Trace};
 use std::slice::Iter;
 
-pub fn run(part: Part, file_path: &str) {
+pub const PARAMS: &[Param] = &[
+    Param {
+        name: "small",
+        kind: Kind::Int,
+        default: "100000",
+        min: 0,
+        max: u64::MAX,
+        help: "Largest size of the dirs summed up in part1",
+    },
+    Param {
+        name: "disk",
+        kind: Kind::Int,
+        default: "70000000",
+        min: 1,
+        max: u64::MAX,
+        help: "Total size of the disk",
+    },
+    Param {
+        name: "needed",
+        kind: Kind::Int,
+        default: "30000000",
+        min: 0,
+        max: u64::MAX,
+        help: "Free space needed for the update",
+    },
+];
+
+pub fn run(part: Part, ctx: &mut Context) -> Option<String> {
     match part {
-        Part::Part1 => part1(file_path),
-        Part::Part2 => part2(file_path),
+        Part::Part1 => Some(part1(ctx)),
+        Part::Part2 => Some(part2(ctx)),
+    }
+}
+
+pub fn check(input: &str) -> Vec<Problem> {
+    let mut problems = vec![];
+    let mut in_ls = false;
+    let mut depth = 0;
+    for (i, line) in input.lines().enumerate() {
+        if i == 0 && line != "$ cd /" {
+            problems.push(Problem::at(i, "expected to start with \"$ cd /\""));
+        }
+        match line.strip_prefix("$ ") {
+            Some("ls") => in_ls = true,
+            Some("cd /") => {
+                in_ls = false;
+                if i > 0 {
+                    problems.push(Problem::at(i, "\"cd /\" is only supported as the first command"));
+                }
+            }
+            Some("cd ..") => {
+                in_ls = false;
+                if depth == 0 {
+                    problems.push(Problem::at(i, "\"cd ..\" from the root directory"));
+                } else {
+                    depth -= 1;
+                }
+            }
+            Some(cmd) if cmd.starts_with("cd ") => {
+                in_ls = false;
+                depth += 1;
+            }
+            Some(cmd) => problems.push(Problem::at(i, format!("unknown command: {:?}", cmd))),
+            None if !in_ls => problems.push(Problem::at(i, "output without a preceding \"$ ls\"")),
+            None if line.starts_with("dir ") => (),
+            None => match line.split_once(' ') {
+                Some((size, _)) if size.parse::<u64>().is_ok() => (),
+                _ => problems.push(Problem::at(
+                    i,
+                    format!("expected \"dir <name>\" or \"<size> <name>\", got {:?}", line),
+                )),
+            },
+        }
     }
+    problems
 }
 
 #[derive(Debug, Clone)]
@@ -27,14 +99,12 @@ impl Command {
     }
 }
 
-fn parse_commands(file_path: &str) -> Vec<Command> {
-    let file = fs::File::open(file_path).expect("Read the input file");
-    let reader = io::BufReader::new(file);
-
+fn parse_commands(ctx: &mut Context) -> Vec<Command> {
     let mut commands = vec![];
     let mut current = Command::new();
     let mut lines = 0;
-    for line in reader.lines().map(|l| l.unwrap()) {
+    let bytes = ctx.input.bytes();
+    for line in bytes.lines() {
         if line.starts_with("$ ") {
             if lines > 0 {
                 commands.push(current.clone());
@@ -51,15 +121,15 @@ fn parse_commands(file_path: &str) -> Vec<Command> {
                 }
             }
         } else {
-            current.output_lines.push(line);
+            current.output_lines.push(line.to_string());
         }
         lines += 1;
     }
 
     commands.push(current);
 
-    println!("Lines: {}", lines);
-    println!("Commands: {}", commands.len());
+    writeln!(ctx.out, "Lines: {}", lines);
+    writeln!(ctx.out, "Commands: {}", commands.len());
     commands
 }
 
@@ -73,20 +143,25 @@ impl Dir {
     fn new(name: &str, size: u64) -> Self { Self { name: name.to_string(), size } }
 }
 
-fn calc_sizes_of_dirs(commands: Vec<Command>) -> Vec<Dir> {
+fn calc_sizes_of_dirs(commands: Vec<Command>, trace: &mut Trace) -> Vec<Dir> {
     let mut iter = commands.iter();
     let mut all_dirs = vec![];
-    calc_sizes_of_dirs_rec(&mut iter, &mut all_dirs, "/");
-    return all_dirs;
+    calc_sizes_of_dirs_rec(&mut iter, &mut all_dirs, "/", trace);
+    all_dirs
 }
 
-fn calc_sizes_of_dirs_rec(iter: &mut Iter<Command>, all_dirs: &mut Vec<Dir>, name: &str) -> u64 {
+fn calc_sizes_of_dirs_rec(
+    iter: &mut Iter<Command>,
+    all_dirs: &mut Vec<Dir>,
+    name: &str,
+    trace: &mut Trace,
+) -> u64 {
     let mut sum = 0;
     loop {
         match iter.next() {
-            Some(cmd) => match (cmd.exe.as_str(), cmd.arg.as_ref().map(|s| s.as_str())) {
+            Some(cmd) => match (cmd.exe.as_str(), cmd.arg.as_deref()) {
                 ("cd", Some("..")) => {
-                    println!("{} $ cd ..", name);
+                    explain!(trace, "{} $ cd .. ({} total)", name, sum);
                     all_dirs.push(Dir::new(name, sum));
                     return sum;
                 }
@@ -96,8 +171,8 @@ fn calc_sizes_of_dirs_rec(iter: &mut Iter<Command>, all_dirs: &mut Vec<Dir>, nam
                         ("/", dir) => format!("/{}", dir),
                         (name, dir) => format!("{}/{}", name, dir),
                     };
-                    println!("{} $ cd {}", name, dir);
-                    sum += calc_sizes_of_dirs_rec(iter, all_dirs, &new_name);
+                    explain!(trace, "{} $ cd {}", name, dir);
+                    sum += calc_sizes_of_dirs_rec(iter, all_dirs, &new_name, trace);
                 }
                 ("ls", None) => {
                     for line in &cmd.output_lines {
@@ -123,36 +198,57 @@ fn calc_sizes_of_dirs_rec(iter: &mut Iter<Command>, all_dirs: &mut Vec<Dir>, nam
     }
 }
 
-fn part1(file_path: &str) {
-    let commands = parse_commands(file_path);
+fn emit_sizes(ctx: &mut Context, dirs: &[Dir]) {
+    ctx.artifacts.emit("dir-sizes", artifact::Kind::Csv, || {
+        let mut csv = "dir,size\n".to_string();
+        for dir in dirs {
+            csv.push_str(&format!("{},{}\n", dir.name, dir.size));
+        }
+        csv
+    });
+}
+
+fn part1(ctx: &mut Context) -> String {
+    let commands = parse_commands(ctx);
+    ctx.parsed();
 
-    let all_dirs = calc_sizes_of_dirs(commands);
+    let all_dirs = calc_sizes_of_dirs(commands, &mut ctx.trace);
+    emit_sizes(ctx, &all_dirs);
+    let small = ctx.params.int("small");
     let mut sum = 0;
     for dir in all_dirs {
-        if dir.size <= 100000 {
-            println!("Dir is small enough: {}\t{}", dir.name, dir.size);
+        if dir.size <= small {
+            writeln!(ctx.out, "Dir is small enough: {}\t{}", dir.name, dir.size);
             sum += dir.size;
         }
     }
-    println!("Sum of sizes: {}", sum);
+    writeln!(ctx.out, "Sum of sizes: {}", sum);
+    sum.to_string()
 }
 
-fn part2(file_path: &str) {
-    let commands = parse_commands(file_path);
+fn part2(ctx: &mut Context) -> String {
+    let commands = parse_commands(ctx);
+    ctx.parsed();
 
-    let all_dirs = calc_sizes_of_dirs(commands);
+    let all_dirs = calc_sizes_of_dirs(commands, &mut ctx.trace);
+    emit_sizes(ctx, &all_dirs);
 
-    let storage_total = 70000000;
+    let storage_total = ctx.params.int("disk");
     let storage_used = all_dirs.iter().find(|dir| dir.name == "/").unwrap().size;
-    let storage_avail = storage_total - storage_used;
-    let storage_avail_goal = 30000000;
-    let storage_to_remove = storage_avail_goal - storage_avail;
+    let storage_avail = storage_total.checked_sub(storage_used).unwrap_or_else(|| {
+        panic!(
+            "{} used doesn't fit on a disk of {}",
+            storage_used, storage_total
+        )
+    });
+    let storage_avail_goal = ctx.params.int("needed");
+    let storage_to_remove = storage_avail_goal.saturating_sub(storage_avail);
 
-    println!("Storage size:        {}", storage_total);
-    println!("Storage used:        {}", storage_used);
-    println!("Available:           {}", storage_avail);
-    println!("Target availability: {}", storage_avail_goal);
-    println!("Minimum to remove:   {}", storage_to_remove);
+    writeln!(ctx.out, "Storage size:        {}", storage_total);
+    writeln!(ctx.out, "Storage used:        {}", storage_used);
+    writeln!(ctx.out, "Available:           {}", storage_avail);
+    writeln!(ctx.out, "Target availability: {}", storage_avail_goal);
+    writeln!(ctx.out, "Minimum to remove:   {}", storage_to_remove);
 
     let mut smallest_candidate: Option<Dir> = None;
     for dir in all_dirs {
@@ -166,5 +262,7 @@ fn part2(file_path: &str) {
         }
     }
 
-    println!("Smallest to remove: {:?}", smallest_candidate.unwrap());
+    let smallest = smallest_candidate.unwrap();
+    writeln!(ctx.out, "Smallest to remove: {:?}", smallest);
+    smallest.size.to_string()
 }
@@ -1,14 +1,49 @@
-use crate::day::Part;
+use crate::check::Problem;
+use crate::day::{Context, Part};
+use crate::generate::Rng;
+use crate::input::Input;
+use crate::trace::explain;
 use std::fmt::Debug;
-use std::fs;
-use std::io::{self, prelude::*};
 use std::ops::Range;
 
-pub fn run(part: Part, file_path: &str) {
+pub const IMPLS: &[&str] = &["naive", "sweep"];
+
+pub fn run(part: Part, ctx: &mut Context) -> Option<String> {
     match part {
-        Part::Part1 => part1(file_path),
-        Part::Part2 => part2(file_path),
+        Part::Part1 => Some(part1(ctx)),
+        Part::Part2 => Some(part2(ctx)),
+    }
+}
+
+pub fn check(input: &str) -> Vec<Problem> {
+    let mut problems = vec![];
+    let width = input.lines().next().unwrap_or("").len();
+    if width == 0 {
+        problems.push(Problem::at(0, "empty first row"));
+    }
+    for (i, line) in input.lines().enumerate() {
+        if let Some(c) = line.chars().find(|c| !c.is_ascii_digit()) {
+            problems.push(Problem::at(i, format!("not a tree height: {:?}", c)));
+        }
+        if line.len() != width {
+            problems.push(Problem::at(
+                i,
+                format!("row is {} trees wide, expected {}", line.len(), width),
+            ));
+        }
+    }
+    problems
+}
+
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        for _ in 0..size {
+            input.push((b'0' + rng.below(10) as u8) as char);
+        }
+        input.push('\n');
     }
+    input
 }
 
 struct Grid {
@@ -26,12 +61,10 @@ impl Grid {
         }
     }
 
-    fn read(file_path: &str) -> Grid {
-        let file = fs::File::open(file_path).expect("Read the input file");
-        let reader = io::BufReader::new(file);
-
+    fn read(input: &Input) -> Grid {
         let mut vec: Vec<Vec<u8>> = vec![];
-        for line in reader.lines().map(|l| l.unwrap()) {
+        let bytes = input.bytes();
+        for line in bytes.lines() {
             let mut line_vec: Vec<u8> = vec![];
             for c in line.chars() {
                 let digit = c.to_digit(10).unwrap() as u8;
@@ -92,6 +125,31 @@ impl Grid {
         visible_count
     }
 
+    /// Same as [`Grid::visible_trees_count`], but sweeps each row and column
+    /// from both ends once, keeping track of the tallest tree seen so far.
+    fn visible_trees_count_sweep(&self) -> usize {
+        let mut visible = vec![vec![false; self.width]; self.height];
+        let mut sweep = |cells: &mut dyn Iterator<Item = (usize, usize)>| {
+            let mut tallest: Option<u8> = None;
+            for (x, y) in cells {
+                let tree_height = self.get_tree_height(x, y);
+                if tallest.is_none_or(|t| tree_height > t) {
+                    visible[y][x] = true;
+                    tallest = Some(tree_height);
+                }
+            }
+        };
+        for y in 0..self.height {
+            sweep(&mut (0..self.width).map(|x| (x, y)));
+            sweep(&mut (0..self.width).rev().map(|x| (x, y)));
+        }
+        for x in 0..self.width {
+            sweep(&mut (0..self.height).map(|y| (x, y)));
+            sweep(&mut (0..self.height).rev().map(|y| (x, y)));
+        }
+        visible.iter().flatten().filter(|&&v| v).count()
+    }
+
     fn scenic_score(&self, x: usize, y: usize) -> usize {
         let tree_height = self.get_tree_height(x, y);
         self.scenic_score_x_range(tree_height, (0..x).rev(), y)
@@ -113,7 +171,7 @@ impl Grid {
                 break;
             }
         }
-        return count;
+        count
     }
 
     fn scenic_score_y_range<T: IntoIterator<Item = usize>>(
@@ -129,7 +187,7 @@ impl Grid {
                 break;
             }
         }
-        return count;
+        count
     }
 
     fn highest_scenic_score(&self) -> usize {
@@ -155,25 +213,33 @@ impl Debug for Grid {
     }
 }
 
-fn part1(file_path: &str) {
-    let grid = Grid::read(file_path);
-    let visible_count = grid.visible_trees_count();
+fn part1(ctx: &mut Context) -> String {
+    let grid = Grid::read(&ctx.input);
+    ctx.parsed();
+    let visible_count = match ctx.impl_name.as_deref() {
+        Some("sweep") => grid.visible_trees_count_sweep(),
+        _ => grid.visible_trees_count(),
+    };
 
-    println!("Grid: {:?}", grid);
-    println!("Visible trees: {}", visible_count);
+    explain!(ctx.trace, "Read {:?}", grid);
+    writeln!(ctx.out, "Visible trees: {}", visible_count);
+    visible_count.to_string()
 }
 
-fn part2(file_path: &str) {
-    let grid = Grid::read(file_path);
+fn part2(ctx: &mut Context) -> String {
+    let grid = Grid::read(&ctx.input);
+    ctx.parsed();
     let highest_score = grid.highest_scenic_score();
 
-    println!("Grid: {:?}", grid);
-    println!("Highest scenic score: {}", highest_score);
+    explain!(ctx.trace, "Read {:?}", grid);
+    writeln!(ctx.out, "Highest scenic score: {}", highest_score);
+    highest_score.to_string()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day08::Grid;
+    use crate::check::Problem;
+    use crate::day08::{check, Grid};
 
     fn example_grid() -> Grid {
         let vec: Vec<Vec<u8>> = vec![
@@ -189,23 +255,36 @@ mod tests {
     #[test]
     fn test_is_tree_visible_corners() {
         let grid = example_grid();
-        assert_eq!(grid.is_tree_visible(0, 0), true);
-        assert_eq!(grid.is_tree_visible(4, 0), true);
-        assert_eq!(grid.is_tree_visible(0, 4), true);
-        assert_eq!(grid.is_tree_visible(4, 4), true);
+        assert!(grid.is_tree_visible(0, 0));
+        assert!(grid.is_tree_visible(4, 0));
+        assert!(grid.is_tree_visible(0, 4));
+        assert!(grid.is_tree_visible(4, 4));
     }
 
     #[test]
     fn test_is_tree_visible_interior() {
         let grid = example_grid();
-        assert_eq!(grid.is_tree_visible(1, 1), true);
-        assert_eq!(grid.is_tree_visible(3, 1), false);
+        assert!(grid.is_tree_visible(1, 1));
+        assert!(!grid.is_tree_visible(3, 1));
+    }
+
+    #[test]
+    fn test_check() {
+        assert_eq!(check("30373\n25512\n"), vec![]);
+        assert_eq!(
+            check("30373\n2551\n6a332\n"),
+            vec![
+                Problem::at(1, "row is 4 trees wide, expected 5"),
+                Problem::at(2, "not a tree height: 'a'"),
+            ]
+        );
     }
 
     #[test]
     fn test_visible_trees_count() {
         let grid = example_grid();
         assert_eq!(grid.visible_trees_count(), 21);
+        assert_eq!(grid.visible_trees_count_sweep(), 21);
     }
 
     #[test]
@@ -1,13 +1,48 @@
-use crate::day::Part;
-use std::fmt::Display;
-use std::fs;
-use std::io::{self, prelude::*};
-
-pub fn run(part: Part, file_path: &str) {
+use crate::anim::{Animator, Frame};
+use crate::check::Problem;
+use crate::day::{Context, Part};
+use crate::generate::Rng;
+use crate::params::{Kind, Param};
+use std::fmt::{self, Display};
+
+pub const PARAMS: &[Param] = &[Param {
+    name: "knots",
+    kind: Kind::Int,
+    default: "10",
+    min: 1,
+    max: 1_000,
+    help: "Number of knots in the part2 rope, including the head",
+}];
+
+pub fn run(part: Part, ctx: &mut Context) -> Option<String> {
     match part {
-        Part::Part1 => part1(file_path),
-        Part::Part2 => part2(file_path),
+        Part::Part1 => Some(part1(ctx)),
+        Part::Part2 => Some(part2(ctx)),
+    }
+}
+
+pub fn check(input: &str) -> Vec<Problem> {
+    let mut problems = vec![];
+    for (i, line) in input.lines().enumerate() {
+        match Move::try_parse(line) {
+            Some(mv) if mv.steps == 0 => problems.push(Problem::at(i, "move of 0 steps")),
+            Some(_) => (),
+            None => problems.push(Problem::at(
+                i,
+                format!("expected \"<U|R|D|L> <steps>\", got {:?}", line),
+            )),
+        }
+    }
+    problems
+}
+
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        let dir = ['U', 'R', 'D', 'L'][rng.below(4) as usize];
+        input.push_str(&format!("{} {}\n", dir, 1 + rng.below(20)));
     }
+    input
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -78,7 +113,7 @@ impl Point {
 
     fn follow(&mut self, other: &Point) {
         let (dx, dy) = self.delta(other);
-        let (ax, ay) = (dx.abs() as usize, dy.abs() as usize);
+        let (ax, ay) = (dx.unsigned_abs(), dy.unsigned_abs());
         let (sx, sy) = (dx.signum(), dy.signum());
 
         match (ax, ay) {
@@ -112,7 +147,49 @@ impl Display for Point {
     }
 }
 
-fn count_unique_tail_points(moves: Vec<Move>) -> usize {
+/// Draws the area around the head of the rope, with the visited tail points.
+#[cfg_attr(not(feature = "viz"), allow(dead_code))]
+struct RopeFrame<'a> {
+    knots: &'a [Point],
+    visited: &'a [Point],
+}
+
+#[cfg_attr(not(feature = "viz"), allow(dead_code))]
+impl RopeFrame<'_> {
+    const HALF_WIDTH: isize = 20;
+    const HALF_HEIGHT: isize = 10;
+
+    fn knot_char(&self, index: usize) -> char {
+        match index {
+            0 => 'H',
+            1 if self.knots.len() == 2 => 'T',
+            i => char::from_digit(i as u32, 36).unwrap_or('*'),
+        }
+    }
+}
+
+impl Frame for RopeFrame<'_> {
+    fn draw(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        let head = self.knots[0];
+        writeln!(f, "Head: {}, visited: {}", head, self.visited.len())?;
+        for y in (head.y - Self::HALF_HEIGHT..=head.y + Self::HALF_HEIGHT).rev() {
+            for x in head.x - Self::HALF_WIDTH..=head.x + Self::HALF_WIDTH {
+                let point = Point::new(x, y);
+                let c = match self.knots.iter().position(|knot| *knot == point) {
+                    Some(index) => self.knot_char(index),
+                    None if point == Point::new(0, 0) => 's',
+                    None if self.visited.contains(&point) => '#',
+                    None => '.',
+                };
+                write!(f, "{}", c)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+fn count_unique_tail_points(moves: Vec<Move>, anim: &mut Animator) -> usize {
     let mut head = Point::new(0, 0);
     let mut tail = Point::new(0, 0);
     let mut tail_points = vec![tail];
@@ -124,104 +201,108 @@ fn count_unique_tail_points(moves: Vec<Move>) -> usize {
             if !tail_points.contains(&tail) {
                 tail_points.push(tail);
             }
+            anim.step(&RopeFrame {
+                knots: &[head, tail],
+                visited: &tail_points,
+            });
         }
     }
 
+    anim.finish(&RopeFrame {
+        knots: &[head, tail],
+        visited: &tail_points,
+    });
     tail_points.len()
 }
 
 struct Rope {
-    head: Point,
-    tail1: Point,
-    tail2: Point,
-    tail3: Point,
-    tail4: Point,
-    tail5: Point,
-    tail6: Point,
-    tail7: Point,
-    tail8: Point,
-    tail9: Point,
+    knots: Vec<Point>,
 }
 
 impl Rope {
-    fn new() -> Self {
+    fn new(knot_count: usize) -> Self {
+        assert!(knot_count > 0, "A rope needs at least one knot");
         Self {
-            head: Point::new(0, 0),
-            tail1: Point::new(0, 0),
-            tail2: Point::new(0, 0),
-            tail3: Point::new(0, 0),
-            tail4: Point::new(0, 0),
-            tail5: Point::new(0, 0),
-            tail6: Point::new(0, 0),
-            tail7: Point::new(0, 0),
-            tail8: Point::new(0, 0),
-            tail9: Point::new(0, 0),
+            knots: vec![Point::new(0, 0); knot_count],
         }
     }
 
     fn mv_head(&mut self, dir: Direction) {
-        self.head.mv(dir);
-        self.tail1.follow(&self.head);
-        self.tail2.follow(&self.tail1);
-        self.tail3.follow(&self.tail2);
-        self.tail4.follow(&self.tail3);
-        self.tail5.follow(&self.tail4);
-        self.tail6.follow(&self.tail5);
-        self.tail7.follow(&self.tail6);
-        self.tail8.follow(&self.tail7);
-        self.tail9.follow(&self.tail8);
+        self.knots[0].mv(dir);
+        for i in 1..self.knots.len() {
+            let leader = self.knots[i - 1];
+            self.knots[i].follow(&leader);
+        }
+    }
+
+    fn tail(&self) -> Point {
+        *self.knots.last().unwrap()
     }
 }
 
-fn count_unique_tail_points_rope(moves: Vec<Move>) -> usize {
-    let mut rope = Rope::new();
-    let mut tail_points = vec![rope.tail9];
+fn count_unique_tail_points_rope(
+    moves: Vec<Move>,
+    knot_count: usize,
+    anim: &mut Animator,
+) -> usize {
+    let mut rope = Rope::new(knot_count);
+    let mut tail_points = vec![rope.tail()];
 
     for mv in moves {
         for _ in 0..mv.steps {
             rope.mv_head(mv.dir);
-            if !tail_points.contains(&rope.tail9) {
-                tail_points.push(rope.tail9);
+            if !tail_points.contains(&rope.tail()) {
+                tail_points.push(rope.tail());
             }
+            anim.step(&RopeFrame {
+                knots: &rope.knots,
+                visited: &tail_points,
+            });
         }
     }
 
+    anim.finish(&RopeFrame {
+        knots: &rope.knots,
+        visited: &tail_points,
+    });
     tail_points.len()
 }
 
-fn part1(file_path: &str) {
-    let file = fs::File::open(file_path).expect("Read the input file");
-    let reader = io::BufReader::new(file);
-
+fn part1(ctx: &mut Context) -> String {
     let mut moves = vec![];
-    for line in reader.lines().map(|l| l.unwrap()) {
-        let mv = Move::try_parse(&line).unwrap();
+    let bytes = ctx.input.bytes();
+    for line in bytes.lines() {
+        let mv = Move::try_parse(line).unwrap();
         moves.push(mv);
     }
+    ctx.parsed();
 
-    let tail_points_count = count_unique_tail_points(moves);
+    let tail_points_count = count_unique_tail_points(moves, &mut ctx.anim);
 
-    println!("Unique tail points: {}", tail_points_count);
+    writeln!(ctx.out, "Unique tail points: {}", tail_points_count);
+    tail_points_count.to_string()
 }
 
-fn part2(file_path: &str) {
-    let file = fs::File::open(file_path).expect("Read the input file");
-    let reader = io::BufReader::new(file);
-
+fn part2(ctx: &mut Context) -> String {
     let mut moves = vec![];
-    for line in reader.lines().map(|l| l.unwrap()) {
-        let mv = Move::try_parse(&line).unwrap();
+    let bytes = ctx.input.bytes();
+    for line in bytes.lines() {
+        let mv = Move::try_parse(line).unwrap();
         moves.push(mv);
     }
+    ctx.parsed();
 
-    let tail_points_count = count_unique_tail_points_rope(moves);
+    let knot_count = ctx.params.int("knots") as usize;
+    let tail_points_count = count_unique_tail_points_rope(moves, knot_count, &mut ctx.anim);
 
-    println!("Unique tail points: {}", tail_points_count);
+    writeln!(ctx.out, "Unique tail points: {}", tail_points_count);
+    tail_points_count.to_string()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day09::count_unique_tail_points;
+    use crate::anim::Animator;
+    use crate::day09::{count_unique_tail_points, count_unique_tail_points_rope};
 
     use super::{Direction, Move};
 
@@ -241,6 +322,19 @@ mod tests {
     #[test]
     fn test_count_unique_tail_points() {
         let moves = example_moves();
-        assert_eq!(count_unique_tail_points(moves), 13);
+        assert_eq!(count_unique_tail_points(moves, &mut Animator::disabled()), 13);
+    }
+
+    #[test]
+    fn test_count_unique_tail_points_rope() {
+        let moves = example_moves();
+        assert_eq!(
+            count_unique_tail_points_rope(moves.clone(), 2, &mut Animator::disabled()),
+            13
+        );
+        assert_eq!(
+            count_unique_tail_points_rope(moves, 10, &mut Animator::disabled()),
+            1
+        );
     }
 }
@@ -1,13 +1,37 @@
-use crate::day::Part;
-use std::fs;
-use std::io::{self, prelude::*};
+use crate::anim::Frame;
+use crate::artifact;
+use crate::check::Problem;
+use crate::day::{Context, Part};
+use crate::input::Input;
+use crate::params::{Kind, Param};
+use crate::trace::{explain, Trace};
+use std::fmt;
 use std::vec::IntoIter;
 
-pub fn run(part: Part, file_path: &str) {
+pub const PARAMS: &[Param] = &[Param {
+    name: "cycles",
+    kind: Kind::IntList,
+    default: "20,60,100,140,180,220",
+    min: 1,
+    max: u64::MAX,
+    help: "Cycles whose signal strength is summed up in part1",
+}];
+
+pub fn run(part: Part, ctx: &mut Context) -> Option<String> {
     match part {
-        Part::Part1 => part1(file_path),
-        Part::Part2 => part2(file_path),
+        Part::Part1 => Some(part1(ctx)),
+        Part::Part2 => Some(part2(ctx)),
+    }
+}
+
+pub fn check(input: &str) -> Vec<Problem> {
+    let mut problems = vec![];
+    for (i, line) in input.lines().enumerate() {
+        if let Err(err) = Op::parse(line) {
+            problems.push(Problem::at(i, err));
+        }
     }
+    problems
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -35,13 +59,11 @@ impl Op {
         }
     }
 
-    fn read_ops_from_file(file_path: &str) -> Result<Vec<Op>, String> {
-        let file = fs::File::open(file_path).expect("Read the input file");
-        let reader = io::BufReader::new(file);
-
+    fn read_ops(input: &Input) -> Result<Vec<Op>, String> {
         let mut ops = vec![];
-        for line in reader.lines().map(|l| l.unwrap()) {
-            ops.push(Self::parse(&line)?);
+        let bytes = input.bytes();
+        for line in bytes.lines() {
+            ops.push(Self::parse(line)?);
         }
 
         Ok(ops)
@@ -96,9 +118,8 @@ impl Iterator for VM {
             return Some(self.state);
         }
 
-        match self.current_op {
-            Some(op) => op.on_complete(&mut self.state),
-            _ => (),
+        if let Some(op) = self.current_op {
+            op.on_complete(&mut self.state);
         }
 
         self.current_op = self.ops_iter.next();
@@ -112,51 +133,169 @@ impl Iterator for VM {
     }
 }
 
-fn calc_signal_strength(vm: &mut VM) -> i64 {
+fn calc_signal_strength(vm: &mut VM, cycles: &[u64], trace: &mut Trace) -> i64 {
     let mut sum = 0;
     for state in vm {
-        match state.cycle {
-            20 | 60 | 100 | 140 | 180 | 220 => {
-                sum += state.cycle * state.x;
-            }
-            _ => (),
+        if cycles.contains(&(state.cycle as u64)) {
+            sum += state.cycle * state.x;
+            explain!(
+                trace,
+                "Cycle {}: X = {}, signal strength {}",
+                state.cycle,
+                state.x,
+                state.cycle * state.x
+            );
+        } else {
+            explain!(trace, "Cycle {}: X = {}", state.cycle, state.x);
         }
     }
     sum
 }
 
-fn part1(file_path: &str) {
-    let ops = Op::read_ops_from_file(file_path).expect("Read ops from input file");
+fn part1(ctx: &mut Context) -> String {
+    let ops = Op::read_ops(&ctx.input).expect("Read ops from input");
+    ctx.parsed();
     let mut vm = VM::new(ops);
-    let sum = calc_signal_strength(&mut vm);
+    let sum = calc_signal_strength(&mut vm, ctx.params.int_list("cycles"), &mut ctx.trace);
 
-    println!("VM state: {:?}", vm.state);
-    println!("Sum: {}", sum);
+    writeln!(ctx.out, "VM state: {:?}", vm.state);
+    writeln!(ctx.out, "Sum: {}", sum);
+    sum.to_string()
 }
 
-fn part2(file_path: &str) {
-    let ops = Op::read_ops_from_file(file_path).expect("Read ops from input file");
+#[cfg_attr(not(feature = "viz"), allow(dead_code))]
+struct CrtFrame<'a> {
+    state: State,
+    screen: &'a str,
+}
+
+impl Frame for CrtFrame<'_> {
+    fn draw(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        writeln!(f, "Cycle: {:3}, X: {:3}", self.state.cycle, self.state.x)?;
+        let sprite: String = (0..40)
+            .map(|x| if (x - self.state.x).abs() <= 1 { '^' } else { ' ' })
+            .collect();
+        writeln!(f, "{}", sprite)?;
+        write!(f, "{}", self.screen)
+    }
+}
+
+fn part2(ctx: &mut Context) -> String {
+    let ops = Op::read_ops(&ctx.input).expect("Read ops from input");
+    ctx.parsed();
     let mut vm = VM::new(ops);
+    let mut screen = String::new();
 
     for state in &mut vm {
         let sprite_pos = state.x;
         let cursor_x = state.cycle % 40;
-        if cursor_x >= sprite_pos && cursor_x <= sprite_pos + 2 {
-            print!("#");
+        let pixel = if cursor_x >= sprite_pos && cursor_x <= sprite_pos + 2 {
+            '#'
         } else {
-            print!(".");
-        }
+            '.'
+        };
+        explain!(ctx.trace, "Cycle {}: X = {}, draws {}", state.cycle, state.x, pixel);
+        screen.push(pixel);
         if cursor_x == 0 {
-            println!();
+            screen.push('\n');
         }
+        ctx.anim.step(&CrtFrame {
+            state,
+            screen: &screen,
+        });
     }
+    ctx.anim.finish(&CrtFrame {
+        state: vm.state,
+        screen: &screen,
+    });
+
+    ctx.artifacts
+        .emit("crt", artifact::Kind::Text, || screen.clone());
+    ctx.artifacts
+        .emit("crt", artifact::Kind::Svg, || screen_svg(&screen));
 
-    println!("VM state: {:?}", vm.state);
+    write!(ctx.out, "{}", screen);
+    writeln!(ctx.out, "VM state: {:?}", vm.state);
+
+    let letters = read_letters(&screen);
+    writeln!(ctx.out, "Letters: {}", letters);
+    letters
+}
+
+/// Draws the lit pixels of the screen as squares.
+fn screen_svg(screen: &str) -> String {
+    const PIXEL: usize = 10;
+    let rows: Vec<&str> = screen.lines().collect();
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">\n",
+        width * PIXEL,
+        rows.len() * PIXEL
+    );
+    svg.push_str("  <rect width=\"100%\" height=\"100%\" fill=\"#0f0f23\"/>\n");
+    for (y, row) in rows.iter().enumerate() {
+        for (x, _) in row.char_indices().filter(|(_, c)| *c == '#') {
+            svg.push_str(&format!(
+                "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#ffff66\"/>\n",
+                x * PIXEL,
+                y * PIXEL,
+                PIXEL,
+                PIXEL
+            ));
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+// The CRT font is 4 pixels wide and 6 tall, with 1 pixel between letters.
+const LETTERS: [(char, [&str; 6]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...", "#...", ".#.#", "..#.", "..#.", "..#."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+fn read_letters(screen: &str) -> String {
+    let rows: Vec<&str> = screen.lines().collect();
+    // Anything that isn't 6 rows of the same width isn't letters
+    if rows.len() < 6 || rows[1..6].iter().any(|row| row.len() != rows[0].len()) {
+        return String::new();
+    }
+    let mut letters = String::new();
+    for start in (0..rows[0].len()).step_by(5) {
+        let end = (start + 4).min(rows[0].len());
+        let glyph: Vec<&str> = rows[..6].iter().map(|row| &row[start..end]).collect();
+        let letter = LETTERS
+            .iter()
+            .find(|(_, pixels)| pixels[..] == glyph[..])
+            .map(|(c, _)| *c)
+            .unwrap_or('?');
+        letters.push(letter);
+    }
+    letters
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day10::{calc_signal_strength, Op, VM};
+    use crate::day10::{calc_signal_strength, read_letters, screen_svg, Op, VM};
+    use crate::day::Output;
+    use crate::input::Input;
+    use crate::trace::Trace;
 
     #[test]
     fn test_small_program() {
@@ -165,156 +304,48 @@ mod tests {
         assert_eq!(vm.state.x, -1);
     }
 
+    #[test]
+    fn test_read_letters() {
+        let screen = "\
+#..#.####.#....#.....##..
+#..#.#....#....#....#..#.
+####.###..#....#....#..#.
+#..#.#....#....#....#..#.
+#..#.#....#....#....#..#.
+#..#.####.####.####..##..
+";
+        assert_eq!(read_letters(screen), "HELLO");
+        // A short last row
+        assert_eq!(read_letters(&screen[..screen.len() - 4]), "");
+    }
+
+    #[test]
+    fn test_screen_svg() {
+        let svg = screen_svg("#.\n.#\n");
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"20\" height=\"20\">"));
+        assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"10\" height=\"10\" fill=\"#ffff66\"/>"));
+        assert!(svg.contains("<rect x=\"10\" y=\"10\" "));
+        assert_eq!(svg.matches("fill=\"#ffff66\"").count(), 2);
+    }
+
     #[test]
     fn test_large_program() {
-        let mut vm = VM::new(vec![
-            Op::AddX(15),
-            Op::AddX(-11),
-            Op::AddX(6),
-            Op::AddX(-3),
-            Op::AddX(5),
-            Op::AddX(-1),
-            Op::AddX(-8),
-            Op::AddX(13),
-            Op::AddX(4),
-            Op::Noop,
-            Op::AddX(-1),
-            Op::AddX(5),
-            Op::AddX(-1),
-            Op::AddX(5),
-            Op::AddX(-1),
-            Op::AddX(5),
-            Op::AddX(-1),
-            Op::AddX(5),
-            Op::AddX(-1),
-            Op::AddX(-35),
-            Op::AddX(1),
-            Op::AddX(24),
-            Op::AddX(-19),
-            Op::AddX(1),
-            Op::AddX(16),
-            Op::AddX(-11),
-            Op::Noop,
-            Op::Noop,
-            Op::AddX(21),
-            Op::AddX(-15),
-            Op::Noop,
-            Op::Noop,
-            Op::AddX(-3),
-            Op::AddX(9),
-            Op::AddX(1),
-            Op::AddX(-3),
-            Op::AddX(8),
-            Op::AddX(1),
-            Op::AddX(5),
-            Op::Noop,
-            Op::Noop,
-            Op::Noop,
-            Op::Noop,
-            Op::Noop,
-            Op::AddX(-36),
-            Op::Noop,
-            Op::AddX(1),
-            Op::AddX(7),
-            Op::Noop,
-            Op::Noop,
-            Op::Noop,
-            Op::AddX(2),
-            Op::AddX(6),
-            Op::Noop,
-            Op::Noop,
-            Op::Noop,
-            Op::Noop,
-            Op::Noop,
-            Op::AddX(1),
-            Op::Noop,
-            Op::Noop,
-            Op::AddX(7),
-            Op::AddX(1),
-            Op::Noop,
-            Op::AddX(-13),
-            Op::AddX(13),
-            Op::AddX(7),
-            Op::Noop,
-            Op::AddX(1),
-            Op::AddX(-33),
-            Op::Noop,
-            Op::Noop,
-            Op::Noop,
-            Op::AddX(2),
-            Op::Noop,
-            Op::Noop,
-            Op::Noop,
-            Op::AddX(8),
-            Op::Noop,
-            Op::AddX(-1),
-            Op::AddX(2),
-            Op::AddX(1),
-            Op::Noop,
-            Op::AddX(17),
-            Op::AddX(-9),
-            Op::AddX(1),
-            Op::AddX(1),
-            Op::AddX(-3),
-            Op::AddX(11),
-            Op::Noop,
-            Op::Noop,
-            Op::AddX(1),
-            Op::Noop,
-            Op::AddX(1),
-            Op::Noop,
-            Op::Noop,
-            Op::AddX(-13),
-            Op::AddX(-19),
-            Op::AddX(1),
-            Op::AddX(3),
-            Op::AddX(26),
-            Op::AddX(-30),
-            Op::AddX(12),
-            Op::AddX(-1),
-            Op::AddX(3),
-            Op::AddX(1),
-            Op::Noop,
-            Op::Noop,
-            Op::Noop,
-            Op::AddX(-9),
-            Op::AddX(18),
-            Op::AddX(1),
-            Op::AddX(2),
-            Op::Noop,
-            Op::Noop,
-            Op::AddX(9),
-            Op::Noop,
-            Op::Noop,
-            Op::Noop,
-            Op::AddX(-1),
-            Op::AddX(2),
-            Op::AddX(-37),
-            Op::AddX(1),
-            Op::AddX(3),
-            Op::Noop,
-            Op::AddX(15),
-            Op::AddX(-21),
-            Op::AddX(22),
-            Op::AddX(-6),
-            Op::AddX(1),
-            Op::Noop,
-            Op::AddX(2),
-            Op::AddX(1),
-            Op::Noop,
-            Op::AddX(-10),
-            Op::Noop,
-            Op::Noop,
-            Op::AddX(20),
-            Op::AddX(1),
-            Op::AddX(2),
-            Op::AddX(2),
-            Op::AddX(-6),
-            Op::AddX(-11),
-            Op::Noop,
-            Op::Noop,
-            Op::Noop,
-        ]);
-        assert_eq!(calc_signal_strength(&mut vm), 13140);
+        let ops = Op::read_ops(&Input::File("inputs/examples/day10.txt".to_string())).unwrap();
+        let mut vm = VM::new(ops);
+        assert_eq!(
+            calc_signal_strength(&mut vm, &[20, 60, 100, 140, 180, 220], &mut Trace::disabled()),
+            13140
+        );
+    }
+
+    #[test]
+    fn test_explain() {
+        let mut vm = VM::new(vec![Op::Noop, Op::AddX(3), Op::AddX(-5)]);
+        let mut trace = Trace::new(Output::Captured(String::new()));
+        assert_eq!(calc_signal_strength(&mut vm, &[4], &mut trace), 16);
+        assert_eq!(
+            trace.take_captured(),
+            "Cycle 1: X = 1\nCycle 2: X = 1\nCycle 3: X = 1\nCycle 4: X = 4, signal strength 16\nCycle 5: X = 4\n"
+        );
     }
 }
@@ -1,76 +1,130 @@
-use crate::day::Part;
+use crate::anim::{Animator, Frame};
+use crate::check::Problem;
+use crate::day::{Context, Part};
+use crate::params::{Kind, Param};
+use crate::trace::explain;
+use std::fmt;
 
-pub fn run(part: Part, file_path: &str) {
+pub const PARAMS: &[Param] = &[
+    Param {
+        name: "rounds",
+        kind: Kind::Int,
+        default: "20",
+        min: 1,
+        max: 100_000,
+        help: "Number of rounds the monkeys play",
+    },
+    Param {
+        name: "relief",
+        kind: Kind::Int,
+        default: "3",
+        min: 1,
+        max: i32::MAX as u64,
+        help: "Worry levels are divided by this after each inspection",
+    },
+];
+
+pub fn run(part: Part, ctx: &mut Context) -> Option<String> {
     match part {
-        Part::Part1 => part1(file_path),
-        Part::Part2 => part2(file_path),
+        Part::Part1 => Some(part1(ctx)),
+        Part::Part2 => part2(ctx),
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, Clone)]
 struct Monkey {
     items: Vec<i32>,
-    operation: fn(old: i32) -> i32,
+    /// The new worry level, or `None` if it doesn't fit.
+    operation: fn(old: i32) -> Option<i32>,
     test_divisible_by: i32,
     target_monkey_if_true: usize,
     target_monkey_if_false: usize,
 }
 
+pub fn check(input: &str) -> Vec<Problem> {
+    let mut problems = vec![];
+    let line_format = regex::Regex::new(concat!(
+        r"^(Monkey \d+:",
+        r"|  Starting items: (\d+(, \d+)*)?",
+        r"|  Operation: new = old [*+] (old|\d+)",
+        r"|  Test: divisible by \d+",
+        r"|    If (true|false): throw to monkey \d+",
+        r"|)$"
+    ))
+    .unwrap();
+    for (i, line) in input.lines().enumerate() {
+        if !line_format.is_match(line) {
+            problems.push(Problem::at(i, format!("not a monkey description: {:?}", line)));
+        }
+    }
+
+    // The input isn't parsed, but it should at least have the same monkeys
+    let monkeys = input.lines().filter(|l| l.starts_with("Monkey ")).count();
+    let expected = get_monkeys().len();
+    if monkeys != expected {
+        problems.push(Problem::new(format!(
+            "{} monkeys, but the hardcoded values have {}",
+            monkeys, expected
+        )));
+    }
+    problems
+}
+
 fn get_monkeys() -> Vec<Monkey> {
     // There's not enough monkeys to need to parse this automatically
     vec![
         Monkey {
             items: vec![59, 74, 65, 86],
-            operation: |old| old * 19,
+            operation: |old| old.checked_mul(19),
             test_divisible_by: 7,
             target_monkey_if_true: 6,
             target_monkey_if_false: 2,
         },
         Monkey {
             items: vec![62, 84, 72, 91, 68, 78, 51],
-            operation: |old| old + 1,
+            operation: |old| old.checked_add(1),
             test_divisible_by: 2,
             target_monkey_if_true: 2,
             target_monkey_if_false: 0,
         },
         Monkey {
             items: vec![78, 84, 96],
-            operation: |old| old + 8,
+            operation: |old| old.checked_add(8),
             test_divisible_by: 19,
             target_monkey_if_true: 6,
             target_monkey_if_false: 5,
         },
         Monkey {
             items: vec![97, 86],
-            operation: |old| old * old,
+            operation: |old| old.checked_mul(old),
             test_divisible_by: 3,
             target_monkey_if_true: 1,
             target_monkey_if_false: 0,
         },
         Monkey {
             items: vec![50],
-            operation: |old| old + 6,
+            operation: |old| old.checked_add(6),
             test_divisible_by: 13,
             target_monkey_if_true: 3,
             target_monkey_if_false: 1,
         },
         Monkey {
             items: vec![73, 65, 69, 65, 51],
-            operation: |old| old * 17,
+            operation: |old| old.checked_mul(17),
             test_divisible_by: 11,
             target_monkey_if_true: 4,
             target_monkey_if_false: 7,
         },
         Monkey {
             items: vec![69, 82, 97, 93, 82, 84, 58, 63],
-            operation: |old| old + 5,
+            operation: |old| old.checked_add(5),
             test_divisible_by: 5,
             target_monkey_if_true: 5,
             target_monkey_if_false: 7,
         },
         Monkey {
             items: vec![81, 78, 82, 76, 79, 80],
-            operation: |old| old + 3,
+            operation: |old| old.checked_add(3),
             test_divisible_by: 17,
             target_monkey_if_true: 3,
             target_monkey_if_false: 4,
@@ -78,13 +132,36 @@ fn get_monkeys() -> Vec<Monkey> {
     ]
 }
 
-fn calc_inspections(monkeys: &mut Vec<Monkey>) -> Vec<usize> {
-    let mut inspect_count = Vec::with_capacity(monkeys.len());
-    for _ in 0..monkeys.len() {
-        inspect_count.push(0);
+#[cfg_attr(not(feature = "viz"), allow(dead_code))]
+struct MonkeysFrame<'a> {
+    round: usize,
+    monkeys: &'a [Monkey],
+    inspect_count: &'a [usize],
+}
+
+impl Frame for MonkeysFrame<'_> {
+    fn draw(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        writeln!(f, "Round {}", self.round)?;
+        for (i, monkey) in self.monkeys.iter().enumerate() {
+            writeln!(
+                f,
+                "Monkey {} ({:4} inspections): {:?}",
+                i, self.inspect_count[i], monkey.items
+            )?;
+        }
+        Ok(())
     }
+}
+
+fn calc_inspections(
+    monkeys: &mut [Monkey],
+    rounds: usize,
+    relief: i32,
+    anim: &mut Animator,
+) -> Vec<usize> {
+    let mut inspect_count = vec![0; monkeys.len()];
 
-    for _ in 0..20 {
+    for round in 1..=rounds {
         for monkey_index in 0..monkeys.len() {
             // I hate rust
             for item_index in 0..monkeys[monkey_index].items.len() {
@@ -92,7 +169,12 @@ fn calc_inspections(monkeys: &mut Vec<Monkey>) -> Vec<usize> {
                 // Just because of Rust's stupid fuckin borrowing shit
                 let (item, next_index) = {
                     let monkey = &monkeys[monkey_index];
-                    let new = (monkey.operation)(monkey.items[item_index]) / 3;
+                    let new = (monkey.operation)(monkey.items[item_index]).unwrap_or_else(|| {
+                        panic!(
+                            "Worry level overflowed in round {}, try more relief or fewer rounds",
+                            round
+                        )
+                    }) / relief;
                     let next_index = if (new % monkey.test_divisible_by) == 0 {
                         monkey.target_monkey_if_true
                     } else {
@@ -106,44 +188,62 @@ fn calc_inspections(monkeys: &mut Vec<Monkey>) -> Vec<usize> {
             }
             inspect_count[monkey_index] += monkeys[monkey_index].items.len();
             monkeys[monkey_index].items.clear();
+            anim.step(&MonkeysFrame {
+                round,
+                monkeys,
+                inspect_count: &inspect_count,
+            });
         }
     }
 
+    anim.finish(&MonkeysFrame {
+        round: rounds,
+        monkeys,
+        inspect_count: &inspect_count,
+    });
     inspect_count
 }
 
-fn part1(_file_path: &str) {
-    println!("Ignoring input file. Going with hardcoded values.");
+fn part1(ctx: &mut Context) -> String {
+    writeln!(ctx.out, "Ignoring input file. Going with hardcoded values.");
     let mut monkeys = get_monkeys();
-    let inspect_count = calc_inspections(&mut monkeys);
+    let rounds = ctx.params.int("rounds") as usize;
+    let relief = i32::try_from(ctx.params.int("relief")).expect("Relief fits in an i32");
+    let inspect_count = calc_inspections(&mut monkeys, rounds, relief, &mut ctx.anim);
 
     let mut inspect_count_with_index: Vec<(usize, usize)> = Vec::with_capacity(inspect_count.len());
     for (i, count) in inspect_count.iter().enumerate() {
-        println!("Monkey {} inspected items {} times.", i, count);
-        inspect_count_with_index.push((i, count.clone()));
+        explain!(ctx.trace, "Monkey {} inspected items {} times.", i, count);
+        inspect_count_with_index.push((i, *count));
     }
     inspect_count_with_index.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
 
     let high_a = inspect_count_with_index[0];
     let high_b = inspect_count_with_index[1];
-    println!(
+    writeln!(
+        ctx.out,
         "Monkey {} and {} inspected the most items.",
         high_a.0, high_b.0
     );
-    println!(
+    let monkey_business = high_a.1 * high_b.1;
+    writeln!(
+        ctx.out,
         "Their counts multiplied: {} * {} = {}",
         high_a.1,
         high_b.1,
-        high_a.1 * high_b.1
+        monkey_business
     );
+    monkey_business.to_string()
 }
 
-fn part2(_file_path: &str) {
-    println!("Ignoring input file. Going with hardcoded values.");
+fn part2(ctx: &mut Context) -> Option<String> {
+    writeln!(ctx.out, "Ignoring input file. Going with hardcoded values.");
+    None
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::anim::Animator;
     use crate::day11::{calc_inspections, Monkey};
 
     #[test]
@@ -151,34 +251,34 @@ mod tests {
         let mut monkeys = vec![
             Monkey {
                 items: vec![79, 98],
-                operation: |old| old * 19,
+                operation: |old| old.checked_mul(19),
                 test_divisible_by: 23,
                 target_monkey_if_true: 2,
                 target_monkey_if_false: 3,
             },
             Monkey {
                 items: vec![54, 65, 75, 74],
-                operation: |old| old + 6,
+                operation: |old| old.checked_add(6),
                 test_divisible_by: 19,
                 target_monkey_if_true: 2,
                 target_monkey_if_false: 0,
             },
             Monkey {
                 items: vec![79, 60, 97],
-                operation: |old| old * old,
+                operation: |old| old.checked_mul(old),
                 test_divisible_by: 13,
                 target_monkey_if_true: 1,
                 target_monkey_if_false: 3,
             },
             Monkey {
                 items: vec![74],
-                operation: |old| old + 3,
+                operation: |old| old.checked_add(3),
                 test_divisible_by: 17,
                 target_monkey_if_true: 0,
                 target_monkey_if_false: 1,
             },
         ];
-        let inspect_count = calc_inspections(&mut monkeys);
+        let inspect_count = calc_inspections(&mut monkeys, 20, 3, &mut Animator::disabled());
         assert_eq!(inspect_count, vec![101, 95, 7, 105,]);
     }
 }
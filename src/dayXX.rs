@@ -1,34 +1,30 @@
-use crate::day::Part;
-use std::fs;
-use std::io::{self, prelude::*};
+use crate::day::{Context, Part};
 
-pub fn run(part: Part, file_path: &str) {
+pub fn run(part: Part, ctx: &mut Context) -> Option<String> {
     match part {
-        Part::Part1 => part1(file_path),
-        Part::Part2 => part2(file_path),
+        Part::Part1 => Some(part1(ctx)),
+        Part::Part2 => Some(part2(ctx)),
     }
 }
 
-fn part1(file_path: &str) {
-    let file = fs::File::open(file_path).expect("Read the input file");
-    let reader = io::BufReader::new(file);
-
+fn part1(ctx: &mut Context) -> String {
     let mut lines = 0;
-    for _line in reader.lines().map(|l| l.unwrap()) {
+    let bytes = ctx.input.bytes();
+    for _line in bytes.lines() {
         lines += 1;
     }
 
-    println!("Lines: {}", lines);
+    writeln!(ctx.out, "Lines: {}", lines);
+    lines.to_string()
 }
 
-fn part2(file_path: &str) {
-    let file = fs::File::open(file_path).expect("Read the input file");
-    let reader = io::BufReader::new(file);
-
+fn part2(ctx: &mut Context) -> String {
     let mut lines = 0;
-    for _line in reader.lines().map(|l| l.unwrap()) {
+    let bytes = ctx.input.bytes();
+    for _line in bytes.lines() {
         lines += 1;
     }
 
-    println!("Lines: {}", lines);
+    writeln!(ctx.out, "Lines: {}", lines);
+    lines.to_string()
 }
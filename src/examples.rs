@@ -0,0 +1,198 @@
+use std::fs;
+
+use crate::day::Part;
+
+/// Where the examples from the puzzle descriptions live, as `dayNN.txt`.
+pub const EXAMPLES_DIR: &str = "inputs/examples";
+
+/// Expected answers for the examples, in the same format as
+/// `inputs/answers.txt`, checked by `tests/examples.rs`.
+pub const EXAMPLE_ANSWERS_PATH: &str = "inputs/examples/answers.txt";
+
+/// Checks a puzzle day like `day12`, which doesn't have to be implemented
+/// yet, so that examples can be imported before starting on a day.
+pub fn parse_day(s: &str) -> Result<String, String> {
+    let day = s.to_ascii_lowercase();
+    match day.strip_prefix("day").map(|n| (n.len(), n.parse::<u32>())) {
+        Some((2, Ok(1..=25))) => Ok(day),
+        _ => Err(format!("expected a day from day01 to day25, got {:?}", s)),
+    }
+}
+
+/// What we can get out of a saved puzzle page.
+#[derive(Debug, PartialEq, Eq)]
+struct Page {
+    /// Every `<pre><code>` block, in order.
+    blocks: Vec<String>,
+    /// The last highlighted `<code><em>` of each part's description, which
+    /// is where the puzzles give the answer for the example.
+    answers: Vec<String>,
+}
+
+fn parse_page(html: &str) -> Page {
+    let mut blocks = vec![];
+    let mut answers = vec![];
+    for article in between(html, "<article", "</article>") {
+        blocks.extend(
+            between(article, "<pre><code>", "</code></pre>")
+                .into_iter()
+                .map(text),
+        );
+        let highlighted = between(article, "<code><em>", "</em></code>")
+            .into_iter()
+            .chain(between(article, "<em><code>", "</code></em>"));
+        // Whichever kind of highlight comes last in the article
+        if let Some(answer) = highlighted.max_by_key(|s| s.as_ptr()) {
+            answers.push(text(answer));
+        }
+    }
+    Page { blocks, answers }
+}
+
+/// Every part of `s` between a `start` and the next `end`.
+fn between<'a>(s: &'a str, start: &str, end: &str) -> Vec<&'a str> {
+    let mut parts = vec![];
+    let mut rest = s;
+    while let Some(i) = rest.find(start) {
+        rest = &rest[i + start.len()..];
+        let Some(j) = rest.find(end) else {
+            break;
+        };
+        parts.push(&rest[..j]);
+        rest = &rest[j + end.len()..];
+    }
+    parts
+}
+
+/// The text of some HTML, without its tags and with entities decoded.
+fn text(html: &str) -> String {
+    let mut stripped = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => stripped.push(c),
+            _ => (),
+        }
+    }
+    stripped
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Replaces the answers for a day in the answers file.
+fn update_answers(existing: &str, day: &str, answers: &[String]) -> String {
+    let prefix = format!("{} ", day);
+    let mut lines: Vec<String> = existing
+        .lines()
+        .filter(|line| !line.starts_with(&prefix))
+        .map(|line| line.to_string())
+        .collect();
+    for (part, answer) in Part::all().iter().zip(answers) {
+        lines.push(format!("{} {} {}", day, part, answer));
+    }
+    // Keep the comments at the top and the days in order
+    let comments = lines.iter().take_while(|l| l.starts_with('#')).count();
+    lines[comments..].sort();
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+/// Imports the example and its expected answers from a saved puzzle page,
+/// taking the `block`th (one-based) code block as the example input.
+pub fn import(day: &str, page_path: &str, block: usize) {
+    let html = fs::read_to_string(page_path).unwrap_or_else(|e| {
+        eprintln!("Couldn't read {}: {}", page_path, e);
+        std::process::exit(1);
+    });
+    let page = parse_page(&html);
+    let Some(example) = page.blocks.get(block - 1) else {
+        eprintln!(
+            "{} has {} code block(s), there's no block {}",
+            page_path,
+            page.blocks.len(),
+            block
+        );
+        std::process::exit(1);
+    };
+
+    let example_path = format!("{}/{}.txt", EXAMPLES_DIR, day);
+    fs::write(&example_path, example).expect("Write the example");
+    println!(
+        "Wrote block {} of {} ({} lines) to {}",
+        block,
+        page.blocks.len(),
+        example.lines().count(),
+        example_path
+    );
+
+    if page.answers.is_empty() {
+        println!("No highlighted answers found");
+        return;
+    }
+    let existing = fs::read_to_string(EXAMPLE_ANSWERS_PATH).unwrap_or_default();
+    let updated = update_answers(&existing, day, &page.answers);
+    fs::write(EXAMPLE_ANSWERS_PATH, updated).expect("Write the example answers");
+    for (part, answer) in Part::all().iter().zip(&page.answers) {
+        println!("{} {}: {}", day, part, answer);
+    }
+    println!("Updated {}", EXAMPLE_ANSWERS_PATH);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::examples::{parse_day, parse_page, text, update_answers};
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Calorie Counting ---</h2>
+<p>For example:</p>
+<pre><code>1000
+2000
+
+<em>3000</em>
+</code></pre>
+<p>This elf is carrying <code><em>6000</em></code> calories, see <code>x &lt; y</code>.</p>
+<pre><code>not &amp; example
+</code></pre>
+<p>Find the elf carrying the most, <em><code>24000</code></em> here.</p>
+</article>
+<p>Your puzzle answer was <code>66306</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>The top three carry <code><em>45000</em></code> calories.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn test_parse_page() {
+        let page = parse_page(PAGE);
+        assert_eq!(page.blocks, vec!["1000\n2000\n\n3000\n", "not & example\n"]);
+        assert_eq!(page.answers, vec!["24000", "45000"]);
+        assert_eq!(text("<b>a</b> &lt;&amp;lt;"), "a <&lt;");
+    }
+
+    #[test]
+    fn test_update_answers() {
+        let existing = "# comment\nday01 part1 1\nday03 part1 3\n";
+        let answers = vec!["20".to_string(), "21".to_string()];
+        assert_eq!(
+            update_answers(existing, "day02", &answers),
+            "# comment\nday01 part1 1\nday02 part1 20\nday02 part2 21\nday03 part1 3\n"
+        );
+        assert_eq!(
+            update_answers(existing, "day01", &answers[..1]),
+            "# comment\nday01 part1 20\nday03 part1 3\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_day() {
+        assert_eq!(parse_day("day12"), Ok("day12".to_string()));
+        assert_eq!(parse_day("Day03"), Ok("day03".to_string()));
+        assert!(parse_day("day26").is_err());
+        assert!(parse_day("day1").is_err());
+        assert!(parse_day("12").is_err());
+    }
+}
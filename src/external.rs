@@ -0,0 +1,212 @@
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+
+use crate::crypt;
+use crate::day::{Day, Part};
+use crate::runner::Outcome;
+
+/// Where external solvers are registered, with lines like
+/// `day01 part1 alice python3 solutions/day01.py`.
+pub const SOLVERS_PATH: &str = "solvers.txt";
+
+/// How often to check whether a solver exited.
+const POLL_INTERVAL: Duration = Duration::from_millis(2);
+
+/// An executable that solves a day and part, written by someone else.
+///
+/// It gets the input on stdin and prints its answer as the last non-empty
+/// line of stdout. Anything printed before that is ignored, no output at all
+/// means the part isn't solved, and a non-zero exit code means it failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solver {
+    pub day: Day,
+    pub part: Part,
+    pub name: String,
+    pub command: Vec<String>,
+}
+
+/// The outcome of running an external solver, where a failure is reported
+/// as [`Outcome::Panicked`] with the exit status and its stderr.
+pub struct ExternalResult {
+    pub day: Day,
+    pub part: Part,
+    pub name: String,
+    pub outcome: Outcome,
+    pub duration: Duration,
+}
+
+pub fn read(path: &str) -> Result<Vec<Solver>, String> {
+    let s = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    parse(&s).map_err(|e| format!("{}: {}", path, e))
+}
+
+fn parse(s: &str) -> Result<Vec<Solver>, String> {
+    let mut solvers = vec![];
+    for (i, line) in s.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        let (Some(day), Some(part), Some(name)) = (fields.next(), fields.next(), fields.next())
+        else {
+            return Err(format!(
+                "line {}: expected \"<day> <part> <name> <command>...\"",
+                i + 1
+            ));
+        };
+        let command: Vec<String> = fields.map(|f| f.to_string()).collect();
+        if command.is_empty() {
+            return Err(format!("line {}: no command for {}", i + 1, name));
+        }
+        solvers.push(Solver {
+            day: Day::from_str(day, true).map_err(|e| format!("line {}: {}", i + 1, e))?,
+            part: Part::from_str(part, true).map_err(|e| format!("line {}: {}", i + 1, e))?,
+            name: name.to_string(),
+            command,
+        });
+    }
+    Ok(solvers)
+}
+
+/// Runs every solver on its day's default input.
+pub fn run_all(solvers: &[Solver], timeout: Option<Duration>) -> Vec<ExternalResult> {
+    solvers
+        .iter()
+        .map(|solver| {
+            let input = crypt::read_input(&solver.day.input_path());
+            let (outcome, duration) = match input {
+                Ok(input) => run(solver, input, timeout),
+                Err(e) => (Outcome::Panicked(e), Duration::ZERO),
+            };
+            ExternalResult {
+                day: solver.day,
+                part: solver.part,
+                name: solver.name.clone(),
+                outcome,
+                duration,
+            }
+        })
+        .collect()
+}
+
+/// Runs a solver on the given input, killing it if it runs longer than the
+/// timeout.
+pub fn run(solver: &Solver, input: Vec<u8>, timeout: Option<Duration>) -> (Outcome, Duration) {
+    let start = Instant::now();
+    let child = Command::new(&solver.command[0])
+        .args(&solver.command[1..])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) => {
+            return (
+                Outcome::Panicked(format!("couldn't start: {}", e)),
+                start.elapsed(),
+            )
+        }
+    };
+
+    // Feed and drain the pipes on their own threads, so a solver that
+    // doesn't read all of its input or prints a lot can't block us
+    let mut stdin = child.stdin.take().unwrap();
+    thread::spawn(move || stdin.write_all(&input));
+    let stdout = read_on_thread(child.stdout.take().unwrap());
+    let stderr = read_on_thread(child.stderr.take().unwrap());
+
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if timeout.is_some_and(|t| start.elapsed() > t) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return (Outcome::TimedOut, start.elapsed());
+            }
+            Ok(None) => thread::sleep(POLL_INTERVAL),
+            Err(e) => return (Outcome::Panicked(e.to_string()), start.elapsed()),
+        }
+    };
+    let duration = start.elapsed();
+
+    let stdout = stdout.join().unwrap_or_default();
+    if !status.success() {
+        let stderr = stderr.join().unwrap_or_default();
+        let message = match stderr.lines().rfind(|l| !l.trim().is_empty()) {
+            Some(line) => format!("{}: {}", status, line.trim()),
+            None => status.to_string(),
+        };
+        return (Outcome::Panicked(message), duration);
+    }
+    let outcome = match stdout.lines().rfind(|l| !l.trim().is_empty()) {
+        Some(answer) => Outcome::Answer(answer.trim().to_string()),
+        None => Outcome::NotSolved,
+    };
+    (outcome, duration)
+}
+
+fn read_on_thread(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut bytes = vec![];
+        let _ = pipe.read_to_end(&mut bytes);
+        String::from_utf8_lossy(&bytes).into_owned()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::day::{Day, Part};
+    use crate::external::{parse, run, Solver};
+    use crate::runner::Outcome;
+
+    fn solver(command: &[&str]) -> Solver {
+        Solver {
+            day: Day::Day01,
+            part: Part::Part1,
+            name: "test".to_string(),
+            command: command.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_solvers() {
+        let solvers = parse("# comment\nday01 part2 alice python3 day01.py --part 2\n").unwrap();
+        assert_eq!(solvers.len(), 1);
+        assert_eq!(solvers[0].day, Day::Day01);
+        assert_eq!(solvers[0].part, Part::Part2);
+        assert_eq!(solvers[0].name, "alice");
+        assert_eq!(solvers[0].command, ["python3", "day01.py", "--part", "2"]);
+        assert!(parse("day01 part1 alice\n").is_err());
+        assert!(parse("day99 part1 alice ./solve\n").is_err());
+    }
+
+    #[test]
+    fn test_run() {
+        let input = b"1\n2\n".to_vec();
+        let (outcome, _) = run(&solver(&["cat"]), input.clone(), None);
+        assert_eq!(outcome, Outcome::Answer("2".to_string()));
+
+        let (outcome, _) = run(&solver(&["printf", "log\\n42\\n\\n"]), vec![], None);
+        assert_eq!(outcome, Outcome::Answer("42".to_string()));
+
+        let (outcome, _) = run(&solver(&["true"]), vec![], None);
+        assert_eq!(outcome, Outcome::NotSolved);
+
+        let (outcome, _) = run(&solver(&["false"]), vec![], None);
+        assert!(matches!(outcome, Outcome::Panicked(_)));
+
+        let (outcome, _) = run(&solver(&["./no-such-solver"]), vec![], None);
+        assert!(matches!(outcome, Outcome::Panicked(_)));
+
+        let timeout = Some(Duration::from_millis(50));
+        let (outcome, _) = run(&solver(&["sleep", "5"]), input, timeout);
+        assert_eq!(outcome, Outcome::TimedOut);
+    }
+}
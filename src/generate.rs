@@ -0,0 +1,37 @@
+/// A small xorshift random number generator, so that generated inputs can
+/// be reproduced from their seed.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // xorshift gets stuck on zero, so scramble the seed first
+        Self(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Returns a number in `0..n`.
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generate::Rng;
+
+    #[test]
+    fn test_rng_is_reproducible() {
+        let a: Vec<u64> = (0..5).scan(Rng::new(7), |rng, _| Some(rng.below(100))).collect();
+        let b: Vec<u64> = (0..5).scan(Rng::new(7), |rng, _| Some(rng.below(100))).collect();
+        let c: Vec<u64> = (0..5).scan(Rng::new(8), |rng, _| Some(rng.below(100))).collect();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert!(a.iter().all(|&n| n < 100));
+    }
+}
@@ -0,0 +1,261 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::process::Command;
+use std::time::Duration;
+
+use clap::ValueEnum;
+
+use crate::day::{Day, Part};
+use crate::runner::format_duration;
+use crate::time::format_date;
+
+/// Where `bench --record` appends its results, one CSV line per day and part.
+pub const BENCH_HISTORY_PATH: &str = "bench-history.csv";
+
+const HEADER: &str = "commit,timestamp,day,part,runs,min_us,median_us,parse_us,solve_us";
+
+/// The benchmark of one day and part at some commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    /// Short hash of the commit, ending in `-dirty` with uncommitted changes.
+    pub commit: String,
+    /// Unix timestamp of the run.
+    pub timestamp: u64,
+    pub day: Day,
+    pub part: Part,
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub parse: Option<Duration>,
+    pub solve: Option<Duration>,
+}
+
+impl Record {
+    fn to_csv(&self) -> String {
+        let micros = |d: Option<Duration>| d.map_or(String::new(), |d| d.as_micros().to_string());
+        format!(
+            "{},{},{},{},{},{},{},{},{}",
+            self.commit,
+            self.timestamp,
+            self.day,
+            self.part,
+            self.runs,
+            self.min.as_micros(),
+            self.median.as_micros(),
+            micros(self.parse),
+            micros(self.solve)
+        )
+    }
+
+    fn from_csv(line: &str) -> Result<Record, String> {
+        let fields: Vec<&str> = line.split(',').collect();
+        let [commit, timestamp, day, part, runs, min, median, parse, solve] = fields[..] else {
+            return Err(format!("expected 9 fields, got {}", fields.len()));
+        };
+        let number = |s: &str| {
+            s.parse::<u64>()
+                .map_err(|_| format!("invalid number {:?}", s))
+        };
+        let micros = |s: &str| number(s).map(Duration::from_micros);
+        let optional = |s: &str| match s {
+            "" => Ok(None),
+            s => micros(s).map(Some),
+        };
+        Ok(Record {
+            commit: commit.to_string(),
+            timestamp: number(timestamp)?,
+            day: Day::from_str(day, true)?,
+            part: Part::from_str(part, true)?,
+            runs: number(runs)? as usize,
+            min: micros(min)?,
+            median: micros(median)?,
+            parse: optional(parse)?,
+            solve: optional(solve)?,
+        })
+    }
+}
+
+/// The commit being benchmarked, as far as git knows.
+pub fn current_commit() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+    };
+    let Some(head) = git(&["rev-parse", "--short", "HEAD"]) else {
+        return "unknown".to_string();
+    };
+    let commit = String::from_utf8_lossy(&head.stdout).trim().to_string();
+    let dirty = git(&["status", "--porcelain", "--untracked-files=no"])
+        .is_some_and(|status| !status.stdout.is_empty());
+    if dirty {
+        format!("{}-dirty", commit)
+    } else {
+        commit
+    }
+}
+
+/// Appends records to the history, starting it with a header if it's new.
+pub fn append(path: &str, records: &[Record]) -> std::io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if file.metadata()?.len() == 0 {
+        writeln!(file, "{}", HEADER)?;
+    }
+    for record in records {
+        writeln!(file, "{}", record.to_csv())?;
+    }
+    Ok(())
+}
+
+fn parse(s: &str) -> Result<Vec<Record>, String> {
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty() && *line != HEADER)
+        .map(|(i, line)| Record::from_csv(line).map_err(|e| format!("line {}: {}", i + 1, e)))
+        .collect()
+}
+
+/// The median runtime at each commit, in the order they were first
+/// benchmarked. Later runs at the same commit replace earlier ones.
+fn by_commit(records: &[&Record]) -> Vec<(String, u64, Duration)> {
+    let mut commits: Vec<(String, u64, Duration)> = vec![];
+    for record in records {
+        match commits.iter_mut().find(|(c, _, _)| *c == record.commit) {
+            Some(entry) => entry.2 = record.median,
+            None => commits.push((record.commit.clone(), record.timestamp, record.median)),
+        }
+    }
+    commits
+}
+
+/// Percentage change from `old` to `new`.
+fn change(old: Duration, new: Duration) -> f64 {
+    (new.as_secs_f64() / old.as_secs_f64().max(1e-9) - 1.0) * 100.0
+}
+
+/// Prints how the median runtime of each day and part changed across
+/// commits, marking the commits where it got more than `threshold` percent
+/// slower than at the commit before.
+pub fn run(days: &[Day], threshold: f64) {
+    let records = fs::read_to_string(BENCH_HISTORY_PATH)
+        .map_err(|e| e.to_string())
+        .and_then(|s| parse(&s))
+        .unwrap_or_else(|e| {
+            eprintln!("Couldn't read {}: {}", BENCH_HISTORY_PATH, e);
+            eprintln!("Record benchmarks with `bench --record` first");
+            std::process::exit(1);
+        });
+
+    let mut slowdowns = 0;
+    for &day in days {
+        for &part in Part::all() {
+            let records: Vec<&Record> = records
+                .iter()
+                .filter(|r| r.day == day && r.part == part)
+                .collect();
+            let commits = by_commit(&records);
+            let (Some(first), Some(last)) = (commits.first(), commits.last()) else {
+                continue;
+            };
+            println!(
+                "{} {}  {} -> {} ({:+.0}%) over {} commit(s)",
+                day,
+                part,
+                format_duration(first.2),
+                format_duration(last.2),
+                change(first.2, last.2),
+                commits.len()
+            );
+            let mut previous: Option<Duration> = None;
+            for (commit, timestamp, median) in &commits {
+                let line = format!(
+                    "    {:<14} {}  {:>10}",
+                    commit,
+                    format_date(*timestamp),
+                    format_duration(*median)
+                );
+                match previous.map(|p| change(p, *median)) {
+                    Some(change) if change > threshold => {
+                        slowdowns += 1;
+                        println!("{}  {:+.0}%  SLOWER", line, change);
+                    }
+                    Some(change) => println!("{}  {:+.0}%", line, change),
+                    None => println!("{}", line),
+                }
+                previous = Some(*median);
+            }
+        }
+    }
+    if slowdowns > 0 {
+        println!(
+            "{} slowdown(s) of more than {}% between commits",
+            slowdowns, threshold
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::day::{Day, Part};
+    use crate::history::{by_commit, change, parse, Record, HEADER};
+
+    fn record(commit: &str, median_ms: u64) -> Record {
+        Record {
+            commit: commit.to_string(),
+            timestamp: 1671500000,
+            day: Day::Day07,
+            part: Part::Part2,
+            runs: 10,
+            min: Duration::from_micros(900),
+            median: Duration::from_millis(median_ms),
+            parse: None,
+            solve: None,
+        }
+    }
+
+    #[test]
+    fn test_csv() {
+        let mut with_phases = record("abc1234", 1);
+        with_phases.parse = Some(Duration::from_micros(300));
+        with_phases.solve = Some(Duration::from_micros(700));
+        let without_phases = record("abc1234-dirty", 2);
+
+        let csv = format!(
+            "{}\n{}\n{}\n",
+            HEADER,
+            with_phases.to_csv(),
+            without_phases.to_csv()
+        );
+        assert_eq!(
+            with_phases.to_csv(),
+            "abc1234,1671500000,day07,part2,10,900,1000,300,700"
+        );
+        assert_eq!(parse(&csv).unwrap(), vec![with_phases, without_phases]);
+        assert!(parse("abc,1,day07,part2\n").is_err());
+    }
+
+    #[test]
+    fn test_by_commit() {
+        let records = [record("a", 1), record("b", 3), record("a", 2)];
+        let records: Vec<&Record> = records.iter().collect();
+        let commits: Vec<(String, Duration)> = by_commit(&records)
+            .into_iter()
+            .map(|(commit, _, median)| (commit, median))
+            .collect();
+        assert_eq!(
+            commits,
+            vec![
+                ("a".to_string(), Duration::from_millis(2)),
+                ("b".to_string(), Duration::from_millis(3))
+            ]
+        );
+        assert_eq!(
+            change(Duration::from_millis(2), Duration::from_millis(3)),
+            50.0
+        );
+    }
+}
@@ -0,0 +1,233 @@
+use std::io::{self, prelude::*, BufReader};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::json;
+
+/// Just enough of HTTP/1.1 to serve JSON: one request per connection, with
+/// a body only if there's a `Content-Length`.
+///
+/// Only listens on localhost, since nothing here is meant to be exposed.
+/// The request line and headers are capped at [`MAX_HEAD`] and bodies at
+/// [`MAX_BODY`], and a client that stops sending is dropped after
+/// [`READ_TIMEOUT`].
+pub struct Server {
+    listener: TcpListener,
+}
+
+/// Big enough for any puzzle input.
+const MAX_BODY: usize = 16 * 1024 * 1024;
+
+/// The request line and all headers together, far more than any client
+/// here sends.
+const MAX_HEAD: u64 = 64 * 1024;
+
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    pub fn new(status: u16, body: String) -> Self {
+        Self { status, body }
+    }
+
+    pub fn error(status: u16, message: &str) -> Self {
+        Self::new(status, format!("{{\"error\":{}}}", json::string(message)))
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            408 => "Request Timeout",
+            413 => "Content Too Large",
+            431 => "Request Header Fields Too Large",
+            422 => "Unprocessable Entity",
+            504 => "Gateway Timeout",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+impl Server {
+    /// Binds to the port on localhost. Port 0 picks any free port.
+    pub fn bind(port: u16) -> io::Result<Server> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        Ok(Server { listener })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.listener.local_addr().expect("Get the server address")
+    }
+
+    /// Answers requests with `handler`, one thread per connection, until
+    /// the listener fails.
+    pub fn run(&self, handler: impl Fn(&Request) -> Response + Send + Sync + 'static) {
+        let handler = Arc::new(handler);
+        for stream in self.listener.incoming() {
+            let Ok(stream) = stream else { continue };
+            let handler = Arc::clone(&handler);
+            thread::spawn(move || {
+                // A client that hangs up early isn't our problem
+                let _ = handle_connection(stream, &*handler);
+            });
+        }
+    }
+}
+
+fn handle_connection(stream: TcpStream, handler: &dyn Fn(&Request) -> Response) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let response = match read_request(&mut reader) {
+        Ok(request) => handler(&request),
+        Err(e) => {
+            let status = match e.kind() {
+                io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => 408,
+                io::ErrorKind::FileTooLarge => 413,
+                io::ErrorKind::QuotaExceeded => 431,
+                _ => 400,
+            };
+            Response::error(status, &e.to_string())
+        }
+    };
+    write_response(stream, &response)
+}
+
+fn read_request(reader: &mut impl BufRead) -> io::Result<Request> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+
+    let mut head_left = MAX_HEAD;
+    let request_line = read_head_line(reader, &mut head_left)?;
+    let mut fields = request_line.split_whitespace();
+    let (method, path) = match (fields.next(), fields.next()) {
+        (Some(method), Some(path)) => (method.to_string(), path.to_string()),
+        _ => return Err(invalid("malformed request line")),
+    };
+
+    let mut content_length = 0;
+    loop {
+        let header = read_head_line(reader, &mut head_left)?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| invalid("invalid Content-Length"))?;
+            }
+        }
+    }
+
+    if content_length > MAX_BODY {
+        return Err(io::Error::new(
+            io::ErrorKind::FileTooLarge,
+            format!("body is over {} bytes", MAX_BODY),
+        ));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    let body = String::from_utf8(body).map_err(|_| invalid("body is not valid UTF-8"))?;
+    Ok(Request { method, path, body })
+}
+
+/// Reads a line of the request line or headers, without reading more than
+/// `left` bytes of them in total.
+fn read_head_line(reader: &mut impl BufRead, left: &mut u64) -> io::Result<String> {
+    let mut line = String::new();
+    let read = reader.by_ref().take(*left).read_line(&mut line)?;
+    *left -= read as u64;
+    if *left == 0 && !line.ends_with('\n') {
+        return Err(io::Error::new(
+            io::ErrorKind::QuotaExceeded,
+            format!("headers are over {} bytes", MAX_HEAD),
+        ));
+    }
+    Ok(line)
+}
+
+fn write_response(mut stream: TcpStream, response: &Response) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.reason(),
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, BufReader, ErrorKind, Read};
+
+    use crate::http::{read_request, MAX_HEAD};
+
+    #[test]
+    fn test_read_request() {
+        let mut raw = "POST /x HTTP/1.1\r\nContent-length: 3\r\n\r\nabcdef".as_bytes();
+        let request = read_request(&mut raw).unwrap();
+        assert_eq!(
+            (
+                request.method.as_str(),
+                request.path.as_str(),
+                request.body.as_str()
+            ),
+            ("POST", "/x", "abc")
+        );
+        assert!(read_request(&mut "nonsense\r\n\r\n".as_bytes()).is_err());
+        assert!(
+            read_request(&mut "GET / HTTP/1.1\r\nContent-Length: x\r\n\r\n".as_bytes()).is_err()
+        );
+        let huge = "POST / HTTP/1.1\r\nContent-Length: 99999999999\r\n\r\n";
+        assert_eq!(
+            read_request(&mut huge.as_bytes()).unwrap_err().kind(),
+            ErrorKind::FileTooLarge
+        );
+    }
+
+    #[test]
+    fn test_read_request_head_limit() {
+        let mut endless_line = BufReader::new(io::repeat(b'a'));
+        assert_eq!(
+            read_request(&mut endless_line).unwrap_err().kind(),
+            ErrorKind::QuotaExceeded
+        );
+
+        let mut endless_header =
+            BufReader::new("GET / HTTP/1.1\r\nX: ".as_bytes().chain(io::repeat(b'a')));
+        assert_eq!(
+            read_request(&mut endless_header).unwrap_err().kind(),
+            ErrorKind::QuotaExceeded
+        );
+
+        let headers = "X: y\r\n".repeat(MAX_HEAD as usize / 6);
+        let raw = format!("GET / HTTP/1.1\r\n{}\r\n", headers);
+        assert_eq!(
+            read_request(&mut raw.as_bytes()).unwrap_err().kind(),
+            ErrorKind::QuotaExceeded
+        );
+
+        let headers = "X: y\r\n".repeat(100);
+        let raw = format!("GET / HTTP/1.1\r\n{}\r\n", headers);
+        assert!(read_request(&mut raw.as_bytes()).is_ok());
+    }
+}
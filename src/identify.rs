@@ -0,0 +1,103 @@
+use std::collections::HashSet;
+use std::fs;
+
+use crate::day::Day;
+
+/// How likely a file is to be the input of a day.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Guess {
+    day: Day,
+    /// From 0 to 1.
+    confidence: f64,
+}
+
+/// Runs every day's input check on the text. The confidence is the share
+/// of lines the check accepts, halved for every problem with the file as a
+/// whole, like a missing section or too few lines.
+fn guess(input: &str) -> Vec<Guess> {
+    let line_count = input.lines().count().max(1);
+    let mut guesses: Vec<Guess> = Day::all()
+        .iter()
+        .map(|&day| {
+            let problems = day.check(input);
+            let bad_lines: HashSet<usize> = problems.iter().filter_map(|p| p.line).collect();
+            let file_problems = problems.iter().filter(|p| p.line.is_none()).count();
+            let accepted = (line_count - bad_lines.len().min(line_count)) as f64;
+            Guess {
+                day,
+                confidence: accepted / line_count as f64 * 0.5f64.powi(file_problems as i32),
+            }
+        })
+        .collect();
+    guesses.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    guesses
+}
+
+/// Prints which days' inputs a file looks like, most likely first.
+pub fn run(path: &str) {
+    let input = fs::read(path)
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| String::from_utf8(bytes).map_err(|_| "not a text file".to_string()))
+        .unwrap_or_else(|e| {
+            eprintln!("Couldn't read {}: {}", path, e);
+            std::process::exit(1);
+        });
+    if input.trim().is_empty() {
+        println!("{} is empty", path);
+        std::process::exit(1);
+    }
+
+    let guesses = guess(&input);
+    for guess in guesses.iter().filter(|g| g.confidence > 0.0) {
+        println!("{}  {:>3.0}%", guess.day, guess.confidence * 100.0);
+    }
+    match guesses.as_slice() {
+        [best, ..] if best.confidence == 0.0 => {
+            println!("{} doesn't look like any day's input", path);
+            std::process::exit(1);
+        }
+        [best, second, ..] if best.confidence == second.confidence => {
+            println!("Can't tell {} and {} apart", best.day, second.day);
+        }
+        [best, ..] => println!("Most likely {}", best.day),
+        [] => {
+            println!("No days compiled in, rebuild with --features all-days");
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::day::Day;
+    use crate::identify::guess;
+
+    #[test]
+    fn test_identify_examples() {
+        for &day in Day::all() {
+            let path = format!("inputs/examples/{}.txt", day);
+            let input = fs::read_to_string(&path).unwrap();
+            let guesses = guess(&input);
+            assert_eq!(guesses[0].day, day, "{} {:?}", path, guesses);
+            // Nothing to compare with when it's the only day compiled in
+            if let Some(second) = guesses.get(1) {
+                assert!(
+                    guesses[0].confidence > second.confidence,
+                    "{:?}",
+                    guesses
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "day07", feature = "day10"))]
+    fn test_identify() {
+        let guesses = guess("noop\naddx 3\naddx -5\n");
+        assert_eq!((guesses[0].day, guesses[0].confidence), (Day::Day10, 1.0));
+        let guesses = guess("$ cd /\n$ ls\ndir a\n14848514 b.txt\n");
+        assert_eq!((guesses[0].day, guesses[0].confidence), (Day::Day07, 1.0));
+    }
+}
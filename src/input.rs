@@ -0,0 +1,138 @@
+use std::fmt::Display;
+use std::fs;
+use std::ops::Deref;
+
+use memmap2::Mmap;
+
+use crate::crypt;
+
+/// The puzzle input handed to a solution.
+///
+/// Files that only exist encrypted are decrypted when read, see
+/// [`crypt::read_input`].
+#[derive(Debug, Clone)]
+pub enum Input {
+    File(String),
+    Text(String),
+}
+
+impl Input {
+    pub fn read_to_string(&self) -> String {
+        match self {
+            Input::File(file_path) => {
+                let bytes = crypt::read_input(file_path).unwrap_or_else(|e| panic!("{}", e));
+                String::from_utf8(bytes).expect("Input is valid UTF-8")
+            }
+            Input::Text(text) => text.clone(),
+        }
+    }
+
+    /// The whole input as bytes, memory-mapped for plain files so even huge
+    /// inputs aren't read into memory up front.
+    pub fn bytes(&self) -> Bytes {
+        match self {
+            Input::File(file_path) => match fs::File::open(file_path) {
+                // Mapping an empty file fails on some platforms
+                Ok(file) if file.metadata().is_ok_and(|m| m.len() > 0) => {
+                    // SAFETY: inputs aren't modified while being solved. If
+                    // one were truncated meanwhile, reading it could crash.
+                    match unsafe { Mmap::map(&file) } {
+                        Ok(mmap) => Bytes::Mapped(mmap),
+                        Err(_) => Bytes::Owned(self.read_to_string().into_bytes()),
+                    }
+                }
+                // Empty, encrypted or missing, none of which can be mapped
+                _ => Bytes::Owned(self.read_to_string().into_bytes()),
+            },
+            Input::Text(text) => Bytes::Owned(text.clone().into_bytes()),
+        }
+    }
+}
+
+/// The bytes of an input, see [`Input::bytes`].
+pub enum Bytes {
+    Mapped(Mmap),
+    Owned(Vec<u8>),
+}
+
+impl Bytes {
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(self).expect("Input is valid UTF-8")
+    }
+
+    /// Iterates over the lines without copying them.
+    pub fn lines(&self) -> Lines<'_> {
+        Lines(self)
+    }
+}
+
+impl Deref for Bytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Bytes::Mapped(mmap) => mmap,
+            Bytes::Owned(bytes) => bytes,
+        }
+    }
+}
+
+/// The lines of some bytes as string slices, split the same way as
+/// [`str::lines`]. Only the lines are checked to be valid UTF-8, so the
+/// input is read once.
+pub struct Lines<'a>(&'a [u8]);
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        if self.0.is_empty() {
+            return None;
+        }
+        let (line, rest) = match self.0.iter().position(|&b| b == b'\n') {
+            Some(i) => (&self.0[..i], &self.0[i + 1..]),
+            None => (self.0, &[][..]),
+        };
+        self.0 = rest;
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        Some(std::str::from_utf8(line).expect("Input is valid UTF-8"))
+    }
+}
+
+impl Display for Input {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Input::File(file_path) => write!(f, "{}", file_path),
+            Input::Text(text) => write!(f, "<{} bytes of text>", text.len()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::{Bytes, Input};
+
+    #[test]
+    fn test_lines() {
+        for text in ["a\nb\r\n\nc", "a\nb\n\nc\n", "", "\n", "x"] {
+            let bytes = Bytes::Owned(text.as_bytes().to_vec());
+            assert_eq!(
+                bytes.lines().collect::<Vec<_>>(),
+                text.lines().collect::<Vec<_>>(),
+                "{:?}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn test_mapped() {
+        let bytes = Input::File("inputs/examples/day04.txt".to_string()).bytes();
+        assert!(matches!(bytes, Bytes::Mapped(_)));
+        assert_eq!(bytes.lines().next(), Some("2-4,6-8"));
+        assert_eq!(
+            bytes.as_str(),
+            std::fs::read_to_string("inputs/examples/day04.txt").unwrap()
+        );
+    }
+}
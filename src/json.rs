@@ -0,0 +1,350 @@
+use std::fmt::{Display, Write};
+
+/// Quotes and escapes a string as a JSON string literal.
+pub fn string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(quoted, "\\u{:04x}", c as u32).unwrap(),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// A JSON string literal, or `null`.
+#[cfg_attr(not(feature = "server"), allow(dead_code))]
+pub fn optional_string(s: Option<&str>) -> String {
+    s.map_or("null".to_string(), string)
+}
+
+/// A JSON number, or `null`.
+#[cfg_attr(not(feature = "server"), allow(dead_code))]
+pub fn optional_number<T: Display>(n: Option<T>) -> String {
+    n.map_or("null".to_string(), |n| n.to_string())
+}
+
+/// A parsed JSON value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    /// Members in the order they appear in.
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// The member of an object with the given key.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(members) => members.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn members(&self) -> &[(String, Value)] {
+        match self {
+            Value::Object(members) => members,
+            _ => &[],
+        }
+    }
+}
+
+/// How deeply arrays and objects can nest, to not overflow the stack on
+/// hostile input.
+const MAX_DEPTH: usize = 128;
+
+/// Parses a JSON document.
+pub fn parse(s: &str) -> Result<Value, String> {
+    let mut parser = Parser {
+        chars: s.char_indices().peekable(),
+        depth: 0,
+    };
+    let value = parser.value()?;
+    parser.whitespace();
+    match parser.chars.next() {
+        None => Ok(value),
+        Some((i, _)) => Err(format!("unexpected trailing data at byte {}", i)),
+    }
+}
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+    /// Arrays and objects we're inside of.
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn whitespace(&mut self) {
+        while self
+            .chars
+            .next_if(|(_, c)| c.is_ascii_whitespace())
+            .is_some()
+        {}
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.chars.next() {
+            Some((_, c)) if c == expected => Ok(()),
+            Some((i, c)) => Err(format!(
+                "expected {:?} at byte {}, got {:?}",
+                expected, i, c
+            )),
+            None => Err(format!("expected {:?}, got the end", expected)),
+        }
+    }
+
+    fn literal(&mut self, word: &str, value: Value) -> Result<Value, String> {
+        for c in word.chars() {
+            self.expect(c)?;
+        }
+        Ok(value)
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        self.whitespace();
+        match self.chars.peek() {
+            Some((_, '{' | '[')) => self.nested(),
+            Some((_, '"')) => self.string().map(Value::String),
+            Some((_, 't')) => self.literal("true", Value::Bool(true)),
+            Some((_, 'f')) => self.literal("false", Value::Bool(false)),
+            Some((_, 'n')) => self.literal("null", Value::Null),
+            Some((_, c)) if *c == '-' || c.is_ascii_digit() => self.number(),
+            Some((i, c)) => Err(format!("unexpected {:?} at byte {}", c, i)),
+            None => Err("unexpected end of input".to_string()),
+        }
+    }
+
+    fn nested(&mut self) -> Result<Value, String> {
+        if self.depth == MAX_DEPTH {
+            return Err(format!("nested more than {} levels deep", MAX_DEPTH));
+        }
+        self.depth += 1;
+        let value = match self.chars.peek() {
+            Some((_, '{')) => self.object(),
+            _ => self.array(),
+        };
+        self.depth -= 1;
+        value
+    }
+
+    fn object(&mut self) -> Result<Value, String> {
+        self.expect('{')?;
+        let mut members = vec![];
+        self.whitespace();
+        if self.chars.next_if(|(_, c)| *c == '}').is_some() {
+            return Ok(Value::Object(members));
+        }
+        loop {
+            self.whitespace();
+            let key = self.string()?;
+            self.whitespace();
+            self.expect(':')?;
+            members.push((key, self.value()?));
+            self.whitespace();
+            if self.chars.next_if(|(_, c)| *c == ',').is_none() {
+                self.expect('}')?;
+                return Ok(Value::Object(members));
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, String> {
+        self.expect('[')?;
+        let mut items = vec![];
+        self.whitespace();
+        if self.chars.next_if(|(_, c)| *c == ']').is_some() {
+            return Ok(Value::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            self.whitespace();
+            if self.chars.next_if(|(_, c)| *c == ',').is_none() {
+                self.expect(']')?;
+                return Ok(Value::Array(items));
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut s = String::new();
+        loop {
+            match self.chars.next() {
+                Some((_, '"')) => return Ok(s),
+                Some((_, '\\')) => match self.chars.next() {
+                    Some((_, 'n')) => s.push('\n'),
+                    Some((_, 'r')) => s.push('\r'),
+                    Some((_, 't')) => s.push('\t'),
+                    Some((_, 'b')) => s.push('\u{8}'),
+                    Some((_, 'f')) => s.push('\u{c}'),
+                    Some((_, 'u')) => self.unicode_escape(&mut s)?,
+                    Some((_, c)) => s.push(c),
+                    None => return Err("unterminated string".to_string()),
+                },
+                Some((_, c)) => s.push(c),
+                None => return Err("unterminated string".to_string()),
+            }
+        }
+    }
+
+    /// The rest of a `\uXXXX` escape, along with the low half that has to
+    /// follow the high half of a UTF-16 surrogate pair. Lone halves become
+    /// U+FFFD.
+    fn unicode_escape(&mut self, s: &mut String) -> Result<(), String> {
+        let code = |code| char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER);
+        let high = self.hex4()?;
+        if !(0xd800..0xdc00).contains(&high) {
+            s.push(code(high));
+            return Ok(());
+        }
+        let mut rest = self.chars.clone();
+        if !matches!(
+            (rest.next(), rest.next()),
+            (Some((_, '\\')), Some((_, 'u')))
+        ) {
+            s.push(char::REPLACEMENT_CHARACTER);
+            return Ok(());
+        }
+        self.chars = rest;
+        let low = self.hex4()?;
+        if (0xdc00..0xe000).contains(&low) {
+            s.push(code(0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)));
+        } else {
+            // Not a pair after all, so the second escape stands on its own
+            s.push(char::REPLACEMENT_CHARACTER);
+            s.push(code(low));
+        }
+        Ok(())
+    }
+
+    fn hex4(&mut self) -> Result<u32, String> {
+        let mut hex = String::new();
+        for _ in 0..4 {
+            match self.chars.next() {
+                Some((_, c)) if c.is_ascii_hexdigit() => hex.push(c),
+                Some((_, c)) => return Err(format!("invalid escape \\u{}{}", hex, c)),
+                None => return Err(format!("invalid escape \\u{}", hex)),
+            }
+        }
+        Ok(u32::from_str_radix(&hex, 16).unwrap())
+    }
+
+    fn number(&mut self) -> Result<Value, String> {
+        let mut s = String::new();
+        while let Some((_, c)) = self
+            .chars
+            .next_if(|(_, c)| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+        {
+            s.push(c);
+        }
+        s.parse()
+            .map(Value::Number)
+            .map_err(|_| format!("invalid number {:?}", s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::json::{optional_number, optional_string, parse, string, Value, MAX_DEPTH};
+
+    #[test]
+    fn test_string() {
+        assert_eq!(string("abc"), "\"abc\"");
+        assert_eq!(string("a \"b\"\n\\"), "\"a \\\"b\\\"\\n\\\\\"");
+        assert_eq!(string("\u{1}"), "\"\\u0001\"");
+        assert_eq!(optional_string(None), "null");
+        assert_eq!(optional_number(Some(12)), "12");
+        assert_eq!(optional_number::<u64>(None), "null");
+    }
+
+    #[test]
+    fn test_parse() {
+        let value =
+            parse(r#" {"a": [1, -2.5e1, true, null], "b": {"c": "x\"\u0041"}, "d": {}} "#).unwrap();
+        assert_eq!(
+            value.get("a"),
+            Some(&Value::Array(vec![
+                Value::Number(1.0),
+                Value::Number(-25.0),
+                Value::Bool(true),
+                Value::Null
+            ]))
+        );
+        assert_eq!(
+            value
+                .get("b")
+                .and_then(|b| b.get("c"))
+                .and_then(|c| c.as_str()),
+            Some("x\"A")
+        );
+        assert_eq!(value.get("d").map(|d| d.members().len()), Some(0));
+        assert_eq!(
+            parse(&string("a\nb")).unwrap(),
+            Value::String("a\nb".to_string())
+        );
+        assert!(parse("{\"a\": 1,}").is_err());
+        assert!(parse("[1] 2").is_err());
+        assert!(parse("\"abc").is_err());
+    }
+
+    #[test]
+    fn test_parse_surrogate_pairs() {
+        assert_eq!(
+            parse(r#""\ud83c\udf84 tree""#).unwrap(),
+            Value::String("\u{1f384} tree".to_string())
+        );
+        assert_eq!(
+            parse(r#""\ud83c!""#).unwrap(),
+            Value::String("\u{fffd}!".to_string())
+        );
+        assert_eq!(
+            parse(r#""\ud83c\u0041""#).unwrap(),
+            Value::String("\u{fffd}A".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_unicode_escapes() {
+        assert_eq!(
+            parse(r#""\u00e9\u00C9""#).unwrap(),
+            Value::String("éÉ".to_string())
+        );
+        assert!(parse(r#""\u1""#).is_err());
+        assert!(parse(r#""\u+123""#).is_err());
+        assert!(parse(r#""\u12"#).is_err());
+        assert!(parse(r#""\ud83c\u+dd8""#).is_err());
+    }
+
+    #[test]
+    fn test_parse_depth() {
+        let nested = |depth| "[".repeat(depth) + &"]".repeat(depth);
+        assert!(parse(&nested(MAX_DEPTH)).is_ok());
+        assert!(parse(&nested(MAX_DEPTH + 1)).is_err());
+        assert!(parse(&"[".repeat(100_000)).is_err());
+    }
+}
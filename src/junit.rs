@@ -0,0 +1,176 @@
+use std::fs;
+
+use crate::runner::{Outcome, RunResult};
+use crate::verify::Answers;
+
+/// How a day and part shows up in the report.
+#[derive(Debug, PartialEq, Eq)]
+enum Case {
+    Passed,
+    Failed { kind: &'static str, message: String },
+    Skipped(&'static str),
+}
+
+fn case(result: &RunResult, answers: Option<&Answers>) -> Case {
+    let expected = answers.and_then(|a| a.get(result.day, result.part));
+    match (&result.outcome, expected) {
+        (Outcome::Answer(answer), Some(expected)) if answer != expected => Case::Failed {
+            kind: "wrong answer",
+            message: format!("expected {}, got {}", expected, answer),
+        },
+        (Outcome::Answer(_), _) => Case::Passed,
+        (Outcome::NotSolved, _) => Case::Skipped("not solved"),
+        (Outcome::Panicked(message), _) => Case::Failed {
+            kind: "panic",
+            message: message.clone(),
+        },
+        (Outcome::TimedOut, _) => Case::Failed {
+            kind: "timeout",
+            message: "timed out".to_string(),
+        },
+    }
+}
+
+/// Escapes text for an attribute. Characters XML 1.0 doesn't allow at all,
+/// like most control characters, become U+FFFD.
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            '\0'..='\u{1f}' | '\u{fffe}' | '\u{ffff}' => escaped.push(char::REPLACEMENT_CHARACTER),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Renders the results as a JUnit XML report with a single test suite,
+/// with one test case per day and part. Wrong answers only fail when
+/// there are `answers` to compare with.
+fn render(suite: &str, results: &[RunResult], answers: Option<&Answers>) -> String {
+    let cases: Vec<Case> = results.iter().map(|r| case(r, answers)).collect();
+    let failures = cases
+        .iter()
+        .filter(|c| matches!(c, Case::Failed { .. }))
+        .count();
+    let skipped = cases
+        .iter()
+        .filter(|c| matches!(c, Case::Skipped(_)))
+        .count();
+    let time: f64 = results.iter().map(|r| r.duration.as_secs_f64()).sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.6}\">\n",
+        results.len(),
+        failures,
+        skipped,
+        time
+    ));
+    xml.push_str(&format!(
+        "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.6}\">\n",
+        escape(suite),
+        results.len(),
+        failures,
+        skipped,
+        time
+    ));
+    for (result, case) in results.iter().zip(&cases) {
+        let open = format!(
+            "    <testcase classname=\"{}\" name=\"{}\" time=\"{:.6}\"",
+            result.day,
+            result.part,
+            result.duration.as_secs_f64()
+        );
+        match case {
+            Case::Passed => xml.push_str(&format!("{}/>\n", open)),
+            Case::Failed { kind, message } => xml.push_str(&format!(
+                "{}>\n      <failure type=\"{}\" message=\"{}\"/>\n    </testcase>\n",
+                open,
+                kind,
+                escape(message)
+            )),
+            Case::Skipped(message) => xml.push_str(&format!(
+                "{}>\n      <skipped message=\"{}\"/>\n    </testcase>\n",
+                open, message
+            )),
+        }
+    }
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+/// Writes the results to `path` as a JUnit XML report, see [`render`].
+pub fn write(path: &str, suite: &str, results: &[RunResult], answers: Option<&Answers>) {
+    match fs::write(path, render(suite, results, answers)) {
+        Ok(()) => println!("Wrote the JUnit report to {}", path),
+        Err(e) => eprintln!("Couldn't write the JUnit report to {}: {}", path, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::day::{Day, Part};
+    use crate::junit::{escape, render};
+    use crate::runner::{Outcome, RunResult};
+    use crate::verify::Answers;
+
+    fn result(day: Day, part: Part, outcome: Outcome) -> RunResult {
+        RunResult {
+            day,
+            part,
+            outcome,
+            duration: Duration::from_micros(1500),
+            cached: false,
+            phases: None,
+            output: String::new(),
+            artifacts: vec![],
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let answers = Answers::parse("day01 part1 1\nday01 part2 2\n").unwrap();
+        let results = vec![
+            result(Day::Day01, Part::Part1, Outcome::Answer("1".to_string())),
+            result(Day::Day01, Part::Part2, Outcome::Answer("3".to_string())),
+            result(
+                Day::Day04,
+                Part::Part1,
+                Outcome::Panicked("<bad> & \"worse\"".to_string()),
+            ),
+            result(Day::Day11, Part::Part2, Outcome::NotSolved),
+        ];
+        assert_eq!(
+            render("verify", &results, Some(&answers)),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites tests="4" failures="2" skipped="1" time="0.006000">
+  <testsuite name="verify" tests="4" failures="2" skipped="1" time="0.006000">
+    <testcase classname="day01" name="part1" time="0.001500"/>
+    <testcase classname="day01" name="part2" time="0.001500">
+      <failure type="wrong answer" message="expected 2, got 3"/>
+    </testcase>
+    <testcase classname="day04" name="part1" time="0.001500">
+      <failure type="panic" message="&lt;bad&gt; &amp; &quot;worse&quot;"/>
+    </testcase>
+    <testcase classname="day11" name="part2" time="0.001500">
+      <skipped message="not solved"/>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+        );
+        // Without answers there's nothing to be wrong about
+        assert!(!render("all", &results[..2], None).contains("<failure"));
+        assert_eq!(escape("a'b"), "a&apos;b");
+        assert_eq!(escape("a\0b\u{1b}[0m\tc\n"), "a\u{fffd}b\u{fffd}[0m\tc\n");
+    }
+}
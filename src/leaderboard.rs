@@ -0,0 +1,313 @@
+use std::fs;
+
+use crate::json::{self, Value};
+use crate::time::{civil_from_days, days_from_civil, format_elapsed};
+
+/// Puzzles unlock at midnight US Eastern time, which is 05:00 UTC.
+const UNLOCK_OFFSET: i64 = 5 * 3600;
+
+const DAY: i64 = 24 * 3600;
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Star {
+    day: u32,
+    part: u32,
+    /// When the star was earned, as a Unix timestamp.
+    ts: i64,
+}
+
+#[derive(Debug)]
+struct Member {
+    name: String,
+    /// Sorted by when they were earned.
+    stars: Vec<Star>,
+}
+
+/// An export of a private leaderboard, from its "API" link.
+#[derive(Debug)]
+struct Leaderboard {
+    year: i64,
+    members: Vec<Member>,
+}
+
+impl Leaderboard {
+    fn parse(s: &str) -> Result<Leaderboard, String> {
+        let root = json::parse(s)?;
+        let year = root
+            .get("event")
+            .and_then(Value::as_str)
+            .and_then(|e| e.parse().ok())
+            .ok_or("no \"event\" year")?;
+        let members = root.get("members").ok_or("no \"members\"")?;
+
+        let mut parsed = vec![];
+        for (id, member) in members.members() {
+            let name = match member.get("name").and_then(Value::as_str) {
+                Some(name) => name.to_string(),
+                None => format!("(anonymous user #{})", id),
+            };
+            let mut stars = vec![];
+            let days = member
+                .get("completion_day_level")
+                .map_or(&[][..], Value::members);
+            for (day, parts) in days {
+                for (part, star) in parts.members() {
+                    let ts = star
+                        .get("get_star_ts")
+                        // Some exports quote it
+                        .and_then(|ts| ts.as_f64().or_else(|| ts.as_str()?.parse().ok()))
+                        .ok_or_else(|| {
+                            format!("{}: no timestamp for day {} part {}", name, day, part)
+                        })?;
+                    stars.push(Star {
+                        day: day.parse().map_err(|_| format!("invalid day {:?}", day))?,
+                        part: part
+                            .parse()
+                            .map_err(|_| format!("invalid part {:?}", part))?,
+                        ts: ts as i64,
+                    });
+                }
+            }
+            stars.sort_by_key(|star| star.ts);
+            parsed.push(Member { name, stars });
+        }
+        Ok(Leaderboard {
+            year,
+            members: parsed,
+        })
+    }
+
+    fn unlock(&self, day: u32) -> i64 {
+        days_from_civil(self.year, 12, day as i64) * DAY + UNLOCK_OFFSET
+    }
+
+    fn last_day(&self) -> u32 {
+        self.members
+            .iter()
+            .flat_map(|m| &m.stars)
+            .map(|star| star.day)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Local scores of every member from the stars earned before `until`.
+    ///
+    /// As on the site, the first member to get a star gets as many points as
+    /// there are members, the second one less, and so on.
+    fn scores(&self, until: i64) -> Vec<u64> {
+        let mut scores = vec![0; self.members.len()];
+        let n = self.members.len() as u64;
+        for day in 1..=self.last_day() {
+            for part in 1..=2 {
+                let mut earned: Vec<(i64, usize)> = self
+                    .members
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, m)| {
+                        m.stars
+                            .iter()
+                            .find(|s| s.day == day && s.part == part)
+                            .map(|s| (s.ts, i))
+                    })
+                    .collect();
+                earned.sort();
+                for (place, (ts, i)) in earned.into_iter().enumerate() {
+                    if ts < until {
+                        scores[i] += n - place as u64;
+                    }
+                }
+            }
+        }
+        scores
+    }
+
+    /// Member indexes from first to last place, by the scores at `until`.
+    ///
+    /// As on the site, ties go to whoever got their last star first.
+    fn ranking(&self, until: i64) -> Vec<usize> {
+        let scores = self.scores(until);
+        let last_star: Vec<i64> = self
+            .members
+            .iter()
+            .map(|m| {
+                m.stars
+                    .iter()
+                    .rev()
+                    .find(|s| s.ts < until)
+                    .map_or(i64::MAX, |s| s.ts)
+            })
+            .collect();
+        let mut ranking: Vec<usize> = (0..self.members.len()).collect();
+        ranking.sort_by(|&a, &b| {
+            scores[b]
+                .cmp(&scores[a])
+                .then(last_star[a].cmp(&last_star[b]))
+                .then_with(|| self.members[a].name.cmp(&self.members[b].name))
+        });
+        ranking
+    }
+
+    /// Seconds from getting part 1 of a day to getting part 2.
+    fn part2_delay(&self, member: usize, day: u32) -> Option<i64> {
+        let stars = &self.members[member].stars;
+        let ts = |part| {
+            stars
+                .iter()
+                .find(|s| s.day == day && s.part == part)
+                .map(|s| s.ts)
+        };
+        Some(ts(2)? - ts(1)?)
+    }
+}
+
+/// Prints the star timelines, part 2 delays and rank changes of a private
+/// leaderboard export.
+pub fn run(path: &str) {
+    let leaderboard = fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|s| Leaderboard::parse(&s))
+        .unwrap_or_else(|e| {
+            eprintln!("Couldn't read the leaderboard {}: {}", path, e);
+            std::process::exit(1);
+        });
+    let final_ranking = leaderboard.ranking(i64::MAX);
+    let scores = leaderboard.scores(i64::MAX);
+
+    println!(">>> Star timelines");
+    for &i in &final_ranking {
+        let member = &leaderboard.members[i];
+        println!(
+            "{} ({} stars, {} points)",
+            member.name,
+            member.stars.len(),
+            scores[i]
+        );
+        for star in &member.stars {
+            println!(
+                "  day{:02} part{}  {}  (+{})",
+                star.day,
+                star.part,
+                format_timestamp(star.ts),
+                format_elapsed(star.ts - leaderboard.unlock(star.day))
+            );
+        }
+    }
+
+    println!();
+    println!(">>> Time from part 1 to part 2");
+    for day in 1..=leaderboard.last_day() {
+        let mut delays: Vec<(i64, &str)> = (0..leaderboard.members.len())
+            .filter_map(|i| {
+                let delay = leaderboard.part2_delay(i, day)?;
+                Some((delay, leaderboard.members[i].name.as_str()))
+            })
+            .collect();
+        if delays.is_empty() {
+            continue;
+        }
+        delays.sort();
+        let delays: Vec<String> = delays
+            .iter()
+            .map(|(delay, name)| format!("{} {}", name, format_elapsed(*delay)))
+            .collect();
+        println!("day{:02}  {}", day, delays.join(", "));
+    }
+
+    println!();
+    println!(">>> Rank at the end of each day");
+    let name_width = leaderboard
+        .members
+        .iter()
+        .map(|m| m.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    let days: Vec<u32> = (1..=leaderboard.last_day()).collect();
+    let header: String = days.iter().map(|day| format!(" {:>3}", day)).collect();
+    println!("{:<name_width$}{}", "", header);
+    let ranks: Vec<Vec<usize>> = days
+        .iter()
+        .map(|&day| {
+            let ranking = leaderboard.ranking(leaderboard.unlock(day) + DAY);
+            let mut ranks = vec![0; ranking.len()];
+            for (place, i) in ranking.into_iter().enumerate() {
+                ranks[i] = place + 1;
+            }
+            ranks
+        })
+        .collect();
+    for &i in &final_ranking {
+        let row: String = ranks
+            .iter()
+            .map(|ranks| format!(" {:>3}", ranks[i]))
+            .collect();
+        println!("{:<name_width$}{}", leaderboard.members[i].name, row);
+    }
+}
+
+/// Formats a timestamp in the puzzles' time zone, like `Dec 01 00:04:12`.
+fn format_timestamp(ts: i64) -> String {
+    let local = ts - UNLOCK_OFFSET;
+    let (_, month, day) = civil_from_days(local.div_euclid(DAY));
+    let secs = local.rem_euclid(DAY);
+    format!(
+        "{} {:02} {:02}:{:02}:{:02}",
+        MONTHS[month as usize - 1],
+        day,
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::leaderboard::{format_timestamp, Leaderboard};
+
+    // Day 1 of 2022 unlocked at 1669870800
+    const EXPORT: &str = r#"{
+        "event": "2022",
+        "owner_id": 1,
+        "members": {
+            "1": {"id": 1, "name": "alice", "stars": 3, "completion_day_level": {
+                "1": {"1": {"get_star_ts": 1669871100, "star_index": 1},
+                      "2": {"get_star_ts": 1669871700, "star_index": 2}},
+                "2": {"1": {"get_star_ts": 1669960000, "star_index": 3}}
+            }},
+            "2": {"id": 2, "name": null, "stars": 3, "completion_day_level": {
+                "1": {"1": {"get_star_ts": 1669871000, "star_index": 4},
+                      "2": {"get_star_ts": 1669872000, "star_index": 5}},
+                "2": {"1": {"get_star_ts": "1669958000", "star_index": 6}}
+            }}
+        }
+    }"#;
+
+    #[test]
+    fn test_leaderboard() {
+        let leaderboard = Leaderboard::parse(EXPORT).unwrap();
+        assert_eq!(leaderboard.year, 2022);
+        assert_eq!(leaderboard.members[1].name, "(anonymous user #2)");
+        assert_eq!(leaderboard.members[1].stars[2].ts, 1669958000);
+        assert_eq!(leaderboard.unlock(1), 1669870800);
+        assert_eq!(leaderboard.last_day(), 2);
+
+        assert_eq!(leaderboard.scores(i64::MAX), vec![4, 5]);
+        // Tied after day 1, and alice got the last star of it first
+        assert_eq!(leaderboard.scores(leaderboard.unlock(2)), vec![3, 3]);
+        assert_eq!(leaderboard.ranking(leaderboard.unlock(2)), vec![0, 1]);
+        assert_eq!(leaderboard.ranking(i64::MAX), vec![1, 0]);
+
+        assert_eq!(leaderboard.part2_delay(0, 1), Some(600));
+        assert_eq!(leaderboard.part2_delay(0, 2), None);
+
+        assert!(Leaderboard::parse("{\"members\": {}}").is_err());
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(1669871100), "Dec 01 00:05:00");
+    }
+}
@@ -1,53 +1,598 @@
-use clap::Parser;
-use day::{Day, Part};
+use std::time::Duration;
 
+#[cfg(feature = "viz")]
+use anim::Animator;
+use artifact::Artifacts;
+use clap::builder::{EnumValueParser, PossibleValue, TypedValueParser};
+use clap::{CommandFactory, Parser, ValueEnum};
+use day::{Context, Day, Output, Part};
+use generate::Rng;
+use input::Input;
+use params::Params;
+use trace::Trace;
+
+// Each day uses a different part of the modules solutions build on, so
+// parts of them go unused unless every day is compiled in
+#[cfg_attr(not(feature = "all-days"), allow(dead_code))]
+mod anim;
+#[cfg_attr(not(feature = "all-days"), allow(dead_code))]
+mod artifact;
+mod bench;
+mod cache;
+mod check;
+mod crosscheck;
+mod crypt;
+#[cfg_attr(not(feature = "all-days"), allow(dead_code, unused_variables))]
 mod day;
+#[cfg(feature = "day01")]
 mod day01;
+#[cfg(feature = "day02")]
 mod day02;
+#[cfg(feature = "day03")]
 mod day03;
+#[cfg(feature = "day04")]
 mod day04;
+#[cfg(feature = "day05")]
 mod day05;
+#[cfg(feature = "day06")]
 mod day06;
+#[cfg(feature = "day07")]
 mod day07;
+#[cfg(feature = "day08")]
 mod day08;
+#[cfg(feature = "day09")]
 mod day09;
+#[cfg(feature = "day10")]
 mod day10;
+#[cfg(feature = "day11")]
 mod day11;
+mod examples;
+mod external;
+#[cfg_attr(not(feature = "all-days"), allow(dead_code))]
+mod generate;
+mod history;
+#[cfg(feature = "net")]
+#[cfg_attr(not(feature = "server"), allow(dead_code))]
+mod http;
+mod identify;
+#[cfg_attr(not(feature = "all-days"), allow(dead_code))]
+mod input;
+#[cfg_attr(not(feature = "all-days"), allow(dead_code))]
+mod json;
+mod junit;
+mod leaderboard;
+mod minimize;
+#[cfg_attr(not(feature = "all-days"), allow(dead_code))]
+mod params;
+mod report;
+mod runner;
+mod scaling;
+#[cfg(feature = "server")]
+mod server;
+mod snapshot;
+mod solve_times;
+mod time;
+#[cfg_attr(
+    not(feature = "all-days"),
+    allow(dead_code, unused_imports, unused_macros)
+)]
+mod trace;
+#[cfg(feature = "tui")]
+mod tui;
+mod verify;
 
 #[derive(Debug, clap::Parser)]
 #[clap(color = concolor_clap::color_choice())]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+#[command(after_help = missing_help())]
 struct Cli {
     #[command(flatten)]
     color: concolor_clap::Color,
 
-    #[arg(value_enum)]
-    day: Day,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[arg(value_parser = DayParser, required = true)]
+    day: Option<Day>,
 
     #[arg(value_enum, default_value_t = Part::Part1)]
     part: Part,
 
     #[arg(short, long)]
     file: Option<String>,
+
+    /// Redraw the simulation in place while solving (day05, day09, day10, day11)
+    #[cfg(feature = "viz")]
+    #[arg(long)]
+    animate: bool,
+
+    /// Frames per second when animating
+    #[cfg(feature = "viz")]
+    #[arg(long, default_value_t = 10, requires = "animate")]
+    fps: u32,
+
+    /// Only draw every Nth simulation step when animating
+    #[cfg(feature = "viz")]
+    #[arg(long, default_value_t = 1, requires = "animate")]
+    skip: usize,
+
+    /// Print each step the solution takes to get to its answer
+    #[arg(long)]
+    explain: bool,
+
+    /// Write what the solution emits besides its answer to this directory (day05, day07, day10)
+    #[arg(long, value_name = "DIR")]
+    artifacts: Option<String>,
+
+    /// Set a day parameter, see the params command for what's available
+    #[arg(short = 'p', long = "param", value_name = "NAME=VALUE")]
+    params: Vec<String>,
+
+    /// Use one of the day's alternative implementations (day06, day08)
+    #[arg(long = "impl")]
+    impl_name: Option<String>,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum Command {
+    /// Run all solutions and print a summary
+    All(RunAllArgs),
+    /// Time each solution over several runs, split into parse and solve time
+    Bench {
+        #[arg(value_parser = DayParser)]
+        day: Option<Day>,
+
+        /// How many times to run each solution
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u64).range(1..))]
+        runs: u64,
+
+        /// Append the results to bench-history.csv, with the current git commit
+        #[arg(long)]
+        record: bool,
+    },
+    /// Show how benchmarks recorded with `bench --record` changed across commits
+    BenchHistory {
+        #[arg(value_parser = DayParser)]
+        day: Option<Day>,
+
+        /// Highlight commits that got slower than this, in percent
+        #[arg(long, default_value_t = 20.0)]
+        threshold: f64,
+    },
+    /// Check the structure of a day's input file without solving it
+    Check {
+        #[arg(value_parser = DayParser)]
+        day: Day,
+
+        #[arg(short, long)]
+        file: Option<String>,
+    },
+    /// Run every implementation of a day and report where they disagree
+    Crosscheck {
+        #[arg(value_parser = DayParser)]
+        day: Day,
+
+        #[arg(short, long, conflicts_with = "generate")]
+        file: Option<String>,
+
+        /// Run on a randomly generated input instead of a file
+        #[arg(long)]
+        generate: bool,
+
+        /// Size of the generated input
+        #[arg(long, default_value_t = 100, requires = "generate")]
+        size: usize,
+
+        /// Seed for the generated input
+        #[arg(long, default_value_t = 1, requires = "generate")]
+        seed: u64,
+    },
+    /// Decrypt every inputs/dayNN.txt.enc next to it, using the key in AOC_INPUT_KEY
+    DecryptInputs,
+    /// Encrypt every input to inputs/dayNN.txt.enc, using the key in AOC_INPUT_KEY
+    EncryptInputs,
+    /// Guess which day an input file is for
+    Identify { file: String },
+    /// Import the example and its answers from a saved puzzle page
+    ImportExamples {
+        /// Any day up to day25, even one that isn't solved yet
+        #[arg(value_parser = examples::parse_day)]
+        day: String,
+
+        /// The puzzle page, saved as HTML
+        page: String,
+
+        /// Which code block on the page is the example, counting from 1
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+        block: u64,
+    },
+    /// Analyze a private leaderboard exported as JSON
+    Leaderboard { file: String },
+    /// Shrink an input a day fails on to the smallest one that fails the same way
+    Minimize {
+        #[arg(value_parser = DayParser)]
+        day: Day,
+
+        /// The input the day panics or times out on
+        file: String,
+
+        /// Only look at this part, instead of the first one that fails
+        #[arg(long, value_enum)]
+        part: Option<Part>,
+
+        /// Where to write the minimal input, defaults to the input's path with .min added
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Count a run longer than this as timing out, e.g. 500ms, 10s or 2m
+        #[arg(long, value_parser = runner::parse_duration, default_value = "10s")]
+        timeout: Duration,
+    },
+    /// Run a day on one candidate input from stdin, for minimize
+    #[command(hide = true)]
+    MinimizeCandidate {
+        #[arg(value_parser = DayParser)]
+        day: Day,
+
+        #[arg(value_enum)]
+        part: Part,
+    },
+    /// List the parameters each day takes with -p
+    Params {
+        #[arg(value_parser = DayParser)]
+        day: Option<Day>,
+    },
+    /// Run all solutions and rewrite the results table in the README
+    Report {
+        /// Only show the first character of each answer
+        #[arg(long)]
+        mask: bool,
+
+        #[arg(long, default_value = "README.md")]
+        readme: String,
+    },
+    /// Time a day on generated inputs of growing size and estimate its complexity
+    Scaling {
+        #[arg(value_parser = DayParser)]
+        day: Day,
+
+        /// Size of the first generated input, doubled for every step
+        #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(u64).range(1..))]
+        from: u64,
+
+        /// Largest number of sizes to try
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u64).range(2..))]
+        steps: u64,
+
+        /// How many times to run each size, keeping the fastest
+        #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u64).range(1..))]
+        runs: u64,
+
+        /// Stop growing the input once a run takes longer than this, e.g. 500ms or 2s
+        #[arg(long, value_parser = runner::parse_duration, default_value = "1s")]
+        budget: Duration,
+    },
+    /// Serve a JSON API for running solutions on localhost
+    #[cfg(feature = "server")]
+    Serve {
+        #[arg(long, default_value_t = 8022)]
+        port: u16,
+
+        /// Give up on a solution that runs longer than this, e.g. 500ms, 10s or 2m
+        #[arg(long, value_parser = runner::parse_duration, default_value = "10s")]
+        timeout: Duration,
+    },
+    /// Show how the output differs from the snapshots in tests/snapshots
+    Snapshots {
+        /// Replace the snapshots with the new output
+        #[arg(long)]
+        accept: bool,
+    },
+    /// Show a full-screen dashboard for running days and parts
+    #[cfg(feature = "tui")]
+    Tui {
+        /// Give up on a solution that runs longer than this, e.g. 500ms, 10s or 2m
+        #[arg(long, value_parser = runner::parse_duration, default_value = "10s")]
+        timeout: Duration,
+    },
+    /// Run all solutions and compare their answers against inputs/answers.txt
+    Verify(RunAllArgs),
+    /// Mark a part as solved, timing it from `start`
+    Done {
+        #[arg(value_parser = DayParser)]
+        day: Day,
+
+        #[arg(value_enum)]
+        part: Part,
+    },
+    /// Start the clock on solving a day
+    Start {
+        #[arg(value_parser = DayParser)]
+        day: Day,
+    },
+    /// Show how long each part took to solve, from `start` to `done` or verify
+    Stats,
+}
+
+#[derive(Debug, clap::Args)]
+struct RunAllArgs {
+    /// Give up on a solution that runs longer than this, e.g. 500ms, 10s or 2m
+    #[arg(long, value_parser = runner::parse_duration)]
+    timeout: Option<Duration>,
+
+    /// Solve everything again instead of using answers cached from earlier runs
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Also run the external solvers in solvers.txt and compare them with ours
+    #[arg(long)]
+    external: bool,
+
+    /// Write the results as a JUnit XML report, one test case per day and part
+    #[arg(long, value_name = "PATH")]
+    junit: Option<String>,
+
+    /// Write what the solutions emit besides their answers to this directory
+    #[arg(long, value_name = "DIR")]
+    artifacts: Option<String>,
+}
+
+/// Parses days like `#[arg(value_enum)]` would, but only offers the days
+/// compiled in and names the feature the others need.
+#[derive(Clone)]
+struct DayParser;
+
+impl TypedValueParser for DayParser {
+    type Value = Day;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &std::ffi::OsStr,
+    ) -> Result<Day, clap::Error> {
+        let day = EnumValueParser::<Day>::new().parse_ref(cmd, arg, value)?;
+        if day.is_compiled() {
+            return Ok(day);
+        }
+        Err(cmd.clone().error(
+            clap::error::ErrorKind::InvalidValue,
+            format!(
+                "{} isn't compiled in, rebuild with --features {}",
+                day,
+                day.feature()
+            ),
+        ))
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(
+            Day::all().iter().filter_map(|day| day.to_possible_value()),
+        ))
+    }
+}
+
+/// Commands and options left out of this build, with the feature each needs.
+fn missing_features() -> Vec<(&'static str, &'static str)> {
+    let mut missing = vec![];
+    if !cfg!(feature = "viz") {
+        missing.extend([("--animate", "viz"), ("--fps", "viz"), ("--skip", "viz")]);
+    }
+    if !cfg!(feature = "server") {
+        missing.push(("serve", "server"));
+    }
+    if !cfg!(feature = "tui") {
+        missing.push(("tui", "tui"));
+    }
+    missing
+}
+
+/// Lists what's left out of this build at the end of `--help`.
+fn missing_help() -> String {
+    let mut lines: Vec<String> = missing_features()
+        .iter()
+        .map(|(name, feature)| format!("  {:<10} needs --features {}", name, feature))
+        .collect();
+    lines.extend(
+        Day::value_variants()
+            .iter()
+            .filter(|day| !day.is_compiled())
+            .map(|day| format!("  {:<10} needs --features {}", day.to_string(), day.feature())),
+    );
+    if lines.is_empty() {
+        return String::new();
+    }
+    format!("Not in this build:\n{}", lines.join("\n"))
 }
 
 fn main() {
+    // Point at the missing feature rather than letting clap call the
+    // command or option unknown
+    let args: Vec<String> = std::env::args().collect();
+    for (name, feature) in missing_features() {
+        let used = match name.strip_prefix("--") {
+            Some(_) => args[1..]
+                .iter()
+                .any(|arg| arg == name || arg.starts_with(&format!("{}=", name))),
+            None => args.get(1).is_some_and(|arg| arg == name),
+        };
+        if used {
+            Cli::command()
+                .error(
+                    clap::error::ErrorKind::InvalidValue,
+                    format!("{} needs the {} feature, rebuild with --features {}", name, feature, feature),
+                )
+                .exit();
+        }
+    }
+
     let cli = Cli::parse();
 
-    let file_path = cli.file.unwrap_or(format!("inputs/{}.txt", cli.day));
-    println!(">>> {}, {}", cli.day, cli.part);
+    match cli.command {
+        Some(Command::All(args)) => verify::run_all(
+            args.timeout,
+            !args.no_cache,
+            args.external,
+            args.junit.as_deref(),
+            args.artifacts.as_deref(),
+        ),
+        Some(Command::Bench { day, runs, record }) => match day {
+            Some(day) => bench::run(&[day], runs as usize, record),
+            None => bench::run(Day::all(), runs as usize, record),
+        },
+        Some(Command::BenchHistory { day, threshold }) => match day {
+            Some(day) => history::run(&[day], threshold),
+            None => history::run(Day::all(), threshold),
+        },
+        Some(Command::Check { day, file }) => {
+            check::run(day, &file.unwrap_or(day.input_path()))
+        }
+        Some(Command::Crosscheck {
+            day,
+            file,
+            generate,
+            size,
+            seed,
+        }) => {
+            let input = if generate {
+                match day.generate(size, &mut Rng::new(seed)) {
+                    Some(text) => Input::Text(text),
+                    None => Cli::command()
+                        .error(
+                            clap::error::ErrorKind::InvalidValue,
+                            format!("{} can't generate inputs", day),
+                        )
+                        .exit(),
+                }
+            } else {
+                Input::File(file.unwrap_or(day.input_path()))
+            };
+            crosscheck::run(day, input)
+        }
+        Some(Command::DecryptInputs) => crypt::decrypt_inputs(),
+        Some(Command::EncryptInputs) => crypt::encrypt_inputs(),
+        Some(Command::Identify { file }) => identify::run(&file),
+        Some(Command::ImportExamples { day, page, block }) => {
+            examples::import(&day, &page, block as usize)
+        }
+        Some(Command::Leaderboard { file }) => leaderboard::run(&file),
+        Some(Command::Minimize {
+            day,
+            file,
+            part,
+            output,
+            timeout,
+        }) => {
+            let output = output.unwrap_or(format!("{}.min", file));
+            minimize::run(day, part, &file, &output, timeout)
+        }
+        Some(Command::MinimizeCandidate { day, part }) => minimize::run_candidate(day, part),
+        Some(Command::Params { day }) => match day {
+            Some(day) => params::print(&[day]),
+            None => params::print(Day::all()),
+        },
+        Some(Command::Report { mask, readme }) => report::run(&readme, mask),
+        Some(Command::Scaling {
+            day,
+            from,
+            steps,
+            runs,
+            budget,
+        }) => {
+            if day.generate(1, &mut Rng::new(1)).is_none() {
+                let generating: Vec<String> = Day::all()
+                    .iter()
+                    .filter(|d| d.generate(1, &mut Rng::new(1)).is_some())
+                    .map(|d| d.to_string())
+                    .collect();
+                Cli::command()
+                    .error(
+                        clap::error::ErrorKind::InvalidValue,
+                        format!(
+                            "{} can't generate inputs, only {} can",
+                            day,
+                            generating.join(", ")
+                        ),
+                    )
+                    .exit();
+            }
+            scaling::run(day, from as usize, steps as usize, runs as usize, budget)
+        }
+        #[cfg(feature = "server")]
+        Some(Command::Serve { port, timeout }) => server::run(port, Some(timeout)),
+        Some(Command::Start { day }) => solve_times::start(day),
+        Some(Command::Done { day, part }) => solve_times::done(day, part),
+        Some(Command::Stats) => solve_times::stats(),
+        Some(Command::Snapshots { accept }) => snapshot::run(accept),
+        #[cfg(feature = "tui")]
+        Some(Command::Tui { timeout }) => tui::run(timeout),
+        Some(Command::Verify(args)) => verify::run_verify(
+            args.timeout,
+            !args.no_cache,
+            args.external,
+            args.junit.as_deref(),
+            args.artifacts.as_deref(),
+        ),
+        None => run_day(cli),
+    }
+}
+
+fn run_day(cli: Cli) {
+    let day = cli.day.expect("Day is required");
+    let file_path = cli.file.unwrap_or(day.input_path());
+    if let Some(name) = &cli.impl_name {
+        if !day.impls().contains(&name.as_str()) {
+            let message = match day.impls() {
+                [] => format!("{} has no alternative implementations", day),
+                impls => format!("{} has implementations: {}", day, impls.join(", ")),
+            };
+            Cli::command()
+                .error(clap::error::ErrorKind::InvalidValue, message)
+                .exit();
+        }
+    }
+
+    let params = Params::parse(day.params(), &cli.params).unwrap_or_else(|e| {
+        Cli::command()
+            .error(clap::error::ErrorKind::InvalidValue, e)
+            .exit()
+    });
+
+    println!(">>> {}, {}", day, cli.part);
     println!(">>> file: {}", file_path);
 
-    match cli.day {
-        Day::Day01 => day01::run(cli.part, &file_path),
-        Day::Day02 => day02::run(cli.part, &file_path),
-        Day::Day03 => day03::run(cli.part, &file_path),
-        Day::Day04 => day04::run(cli.part, &file_path),
-        Day::Day05 => day05::run(cli.part, &file_path),
-        Day::Day06 => day06::run(cli.part, &file_path),
-        Day::Day07 => day07::run(cli.part, &file_path),
-        Day::Day08 => day08::run(cli.part, &file_path),
-        Day::Day09 => day09::run(cli.part, &file_path),
-        Day::Day10 => day10::run(cli.part, &file_path),
-        Day::Day11 => day11::run(cli.part, &file_path),
+    let mut ctx = Context::new(Input::File(file_path));
+    #[cfg(feature = "viz")]
+    if cli.animate {
+        ctx.anim = Animator::new(cli.fps, cli.skip);
+    }
+    if cli.explain {
+        ctx.trace = Trace::new(Output::Stdout);
+    }
+    if cli.artifacts.is_some() {
+        ctx.artifacts = Artifacts::collecting();
+    }
+    ctx.impl_name = cli.impl_name;
+    ctx.params = params;
+
+    let result = runner::run(day, cli.part, &mut ctx);
+    println!(">>> answer: {}", result.outcome);
+    match result.phases {
+        Some(phases) => println!(
+            ">>> time: {} ({})",
+            runner::format_duration(result.duration),
+            phases
+        ),
+        None => println!(">>> time: {}", runner::format_duration(result.duration)),
+    }
+    if let Some(dir) = &cli.artifacts {
+        match artifact::write(dir, day, cli.part, &result.artifacts) {
+            Ok(paths) => {
+                for path in paths {
+                    println!(">>> artifact: {}", path);
+                }
+            }
+            Err(e) => eprintln!("Couldn't write the artifacts to {}: {}", dir, e),
+        }
     }
 }
@@ -0,0 +1,198 @@
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::time::Duration;
+
+use crate::day::{Context, Day, Part};
+use crate::external::{self, Solver};
+use crate::input::Input;
+use crate::runner::{self, Outcome};
+
+/// Start of the line a candidate run prints when the day panics, see
+/// [`run_candidate`].
+const PANICKED: &str = "panicked:";
+
+/// What makes two failures the same: the kind of failure and the panic
+/// message, ignoring any numbers in it since those tend to change as the
+/// input shrinks.
+fn signature(outcome: &Outcome) -> Option<String> {
+    match outcome {
+        Outcome::Panicked(message) => {
+            let mut masked = String::new();
+            for c in message.chars() {
+                if !c.is_ascii_digit() {
+                    masked.push(c);
+                } else if !masked.ends_with('#') {
+                    masked.push('#');
+                }
+            }
+            Some(format!("panicked: {}", masked))
+        }
+        Outcome::TimedOut => Some("timed out".to_string()),
+        Outcome::Answer(_) | Outcome::NotSolved => None,
+    }
+}
+
+/// Delta debugging: removes ever smaller chunks of lines as long as
+/// `fails` still holds for what's left, until no single line can go.
+fn minimize<'a>(lines: &[&'a str], mut fails: impl FnMut(&[&'a str]) -> bool) -> Vec<&'a str> {
+    let mut lines = lines.to_vec();
+    let mut chunks = 2;
+    while lines.len() >= 2 {
+        let chunk_len = lines.len().div_ceil(chunks);
+        let mut removed = false;
+        for start in (0..lines.len()).step_by(chunk_len) {
+            let end = (start + chunk_len).min(lines.len());
+            let rest: Vec<&str> = [&lines[..start], &lines[end..]].concat();
+            if fails(&rest) {
+                lines = rest;
+                chunks = (chunks - 1).max(2);
+                removed = true;
+                break;
+            }
+        }
+        if !removed {
+            if chunk_len == 1 {
+                break;
+            }
+            chunks = (chunks * 2).min(lines.len());
+        }
+    }
+    lines
+}
+
+/// Runs a day on the input from stdin and prints the panic message if it
+/// panics. This is the child process of [`run_on`].
+pub fn run_candidate(day: Day, part: Part) {
+    let mut text = String::new();
+    io::stdin()
+        .read_to_string(&mut text)
+        .expect("Read the candidate from stdin");
+    let ctx = Context::captured(Input::Text(text));
+    let outcome = runner::with_quiet_panics(|| runner::run_isolated(day, part, ctx, None).outcome);
+    if let Outcome::Panicked(message) = outcome {
+        println!("{} {}", PANICKED, message.escape_debug());
+    }
+}
+
+/// Runs a day in a child process, which unlike a thread can be killed when
+/// it times out. Otherwise every candidate that times out would keep a core
+/// busy, until the load makes other candidates time out too.
+fn run_on(day: Day, part: Part, text: String, timeout: Duration) -> Outcome {
+    let exe = env::current_exe().expect("Find the current executable");
+    let solver = Solver {
+        day,
+        part,
+        name: "candidate".to_string(),
+        command: vec![
+            exe.to_string_lossy().into_owned(),
+            "minimize-candidate".to_string(),
+            day.to_string(),
+            part.to_string(),
+        ],
+    };
+    let (outcome, _) = external::run(&solver, text.into_bytes(), Some(timeout));
+    candidate_outcome(outcome)
+}
+
+/// Turns what [`run_candidate`] printed back into the day's outcome. A child
+/// that crashed outright stays a panic with its exit status.
+fn candidate_outcome(outcome: Outcome) -> Outcome {
+    match outcome {
+        Outcome::Answer(line) => match line.strip_prefix(PANICKED) {
+            Some(message) => Outcome::Panicked(message.trim_start().to_string()),
+            None => Outcome::NotSolved,
+        },
+        outcome => outcome,
+    }
+}
+
+fn join(lines: &[&str]) -> String {
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+/// Shrinks an input on which a day fails to the smallest one that still
+/// fails the same way and writes it to `output`. Without `part`, uses the
+/// first part that fails.
+pub fn run(day: Day, part: Option<Part>, path: &str, output: &str, timeout: Duration) {
+    let text = fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("Couldn't read {}: {}", path, e);
+        std::process::exit(1);
+    });
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None => Part::all().to_vec(),
+    };
+    let failure = parts.iter().find_map(|&part| {
+        let outcome = run_on(day, part, text.clone(), timeout);
+        signature(&outcome).map(|signature| (part, outcome, signature))
+    });
+    let Some((part, outcome, expected)) = failure else {
+        println!("{} doesn't fail on {}, nothing to minimize", day, path);
+        std::process::exit(1);
+    };
+    println!("{} {} on {}: {}", day, part, path, outcome);
+
+    let lines: Vec<&str> = text.lines().collect();
+    let mut attempts = 0;
+    let minimal = minimize(&lines, |candidate| {
+        attempts += 1;
+        let outcome = run_on(day, part, join(candidate), timeout);
+        signature(&outcome).as_ref() == Some(&expected)
+    });
+
+    fs::write(output, join(&minimal)).unwrap_or_else(|e| {
+        eprintln!("Couldn't write {}: {}", output, e);
+        std::process::exit(1);
+    });
+    println!(
+        "Shrunk {} line(s) to {} in {} run(s), wrote {}",
+        lines.len(),
+        minimal.len(),
+        attempts,
+        output
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::minimize::{candidate_outcome, minimize, signature};
+    use crate::runner::Outcome;
+
+    #[test]
+    fn test_minimize() {
+        let lines: Vec<String> = (0..100).map(|i| i.to_string()).collect();
+        let lines: Vec<&str> = lines.iter().map(|s| s.as_str()).collect();
+        let fails = |candidate: &[&str]| candidate.contains(&"17") && candidate.contains(&"83");
+        assert_eq!(minimize(&lines, fails), vec!["17", "83"]);
+        assert_eq!(minimize(&lines, |c| c.contains(&"5")), vec!["5"]);
+    }
+
+    #[test]
+    fn test_signature() {
+        assert_eq!(
+            signature(&Outcome::Panicked("woa too big diff: 2, -13".to_string())),
+            signature(&Outcome::Panicked("woa too big diff: 3, -2".to_string()))
+        );
+        assert_ne!(
+            signature(&Outcome::Panicked("a".to_string())),
+            signature(&Outcome::TimedOut)
+        );
+        assert_eq!(signature(&Outcome::Answer("1".to_string())), None);
+    }
+
+    #[test]
+    fn test_candidate_outcome() {
+        assert_eq!(
+            candidate_outcome(Outcome::Answer("panicked: too big\\n2".to_string())),
+            Outcome::Panicked("too big\\n2".to_string())
+        );
+        assert_eq!(
+            candidate_outcome(Outcome::Answer("panicked:".to_string())),
+            Outcome::Panicked(String::new())
+        );
+        assert_eq!(candidate_outcome(Outcome::NotSolved), Outcome::NotSolved);
+        assert_eq!(candidate_outcome(Outcome::TimedOut), Outcome::TimedOut);
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::day::Day;
+
+/// The type of a parameter's values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Int,
+    IntList,
+}
+
+impl Kind {
+    /// Parses a value whose ints all have to be within `min..=max`.
+    fn parse(&self, s: &str, min: u64, max: u64) -> Result<Value, String> {
+        let int = |s: &str| {
+            let n = s
+                .trim()
+                .parse::<u64>()
+                .map_err(|_| format!("expected a non-negative integer, got {:?}", s))?;
+            if n < min || n > max {
+                return Err(format!(
+                    "{} is out of range, expected {}",
+                    n,
+                    range(min, max)
+                ));
+            }
+            Ok(n)
+        };
+        match self {
+            Kind::Int => int(s).map(Value::Int),
+            Kind::IntList => s
+                .split(',')
+                .map(int)
+                .collect::<Result<_, _>>()
+                .map(Value::IntList),
+        }
+    }
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Kind::Int => write!(f, "int"),
+            Kind::IntList => write!(f, "int,..."),
+        }
+    }
+}
+
+/// A knob a day exposes on the command line as `-p name=value`.
+///
+/// Ints, including those in lists, are limited to `min..=max`, so that
+/// solutions don't have to guard against values that make no sense.
+pub struct Param {
+    pub name: &'static str,
+    pub kind: Kind,
+    pub default: &'static str,
+    pub min: u64,
+    pub max: u64,
+    pub help: &'static str,
+}
+
+impl Param {
+    fn parse(&self, s: &str) -> Result<Value, String> {
+        self.kind.parse(s, self.min, self.max)
+    }
+}
+
+fn range(min: u64, max: u64) -> String {
+    match (min, max) {
+        (min, u64::MAX) => format!("at least {}", min),
+        (min, max) => format!("{} to {}", min, max),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Int(u64),
+    IntList(Vec<u64>),
+}
+
+/// Parameter values for a single run.
+#[derive(Debug, Clone, Default)]
+pub struct Params(HashMap<&'static str, Value>);
+
+impl Params {
+    /// Parses `name=value` assignments, checking them against the day's
+    /// parameters.
+    pub fn parse(specs: &[Param], assignments: &[String]) -> Result<Params, String> {
+        let mut params = Params::default();
+        for assignment in assignments {
+            let (name, value) = assignment
+                .split_once('=')
+                .ok_or_else(|| format!("expected name=value, got {:?}", assignment))?;
+            let spec = specs.iter().find(|p| p.name == name).ok_or_else(|| {
+                match specs.iter().map(|p| p.name).collect::<Vec<_>>() {
+                    names if names.is_empty() => "this day has no parameters".to_string(),
+                    names => format!(
+                        "unknown parameter {:?}, expected one of: {}",
+                        name,
+                        names.join(", ")
+                    ),
+                }
+            })?;
+            let value = spec
+                .parse(value)
+                .map_err(|e| format!("invalid value for {}: {}", name, e))?;
+            params.0.insert(spec.name, value);
+        }
+        Ok(params)
+    }
+
+    /// Sets any parameters that weren't given to their defaults.
+    pub fn fill_defaults(&mut self, specs: &[Param]) {
+        for spec in specs {
+            self.0
+                .entry(spec.name)
+                .or_insert_with(|| spec.parse(spec.default).expect("Parse the default"));
+        }
+    }
+
+    pub fn int(&self, name: &str) -> u64 {
+        match self.0.get(name) {
+            Some(Value::Int(n)) => *n,
+            _ => panic!("No int parameter {:?}", name),
+        }
+    }
+
+    pub fn int_list(&self, name: &str) -> &[u64] {
+        match self.0.get(name) {
+            Some(Value::IntList(list)) => list,
+            _ => panic!("No int list parameter {:?}", name),
+        }
+    }
+}
+
+/// Prints the parameters of the given days.
+pub fn print(days: &[Day]) {
+    for day in days {
+        if day.params().is_empty() {
+            continue;
+        }
+        println!("{}", day);
+        for param in day.params() {
+            let name_and_type = format!("{}=<{}>", param.name, param.kind);
+            let range = match (param.min, param.max) {
+                (0, u64::MAX) => String::new(),
+                (min, max) => format!(", {}", range(min, max)),
+            };
+            println!(
+                "  {:<20} {} [default: {}{}]",
+                name_and_type, param.help, param.default, range
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day::Day;
+    use crate::params::{Kind, Param, Params};
+
+    const SPECS: &[Param] = &[
+        Param {
+            name: "rounds",
+            kind: Kind::Int,
+            default: "20",
+            min: 1,
+            max: 1000,
+            help: "",
+        },
+        Param {
+            name: "cycles",
+            kind: Kind::IntList,
+            default: "20,60",
+            min: 1,
+            max: u64::MAX,
+            help: "",
+        },
+    ];
+
+    #[test]
+    fn test_parse_params() {
+        let mut params = Params::parse(SPECS, &["rounds=5".to_string()]).unwrap();
+        params.fill_defaults(SPECS);
+        assert_eq!(params.int("rounds"), 5);
+        assert_eq!(params.int_list("cycles"), &[20, 60]);
+
+        let params = Params::parse(SPECS, &["cycles=1, 2,3".to_string()]).unwrap();
+        assert_eq!(params.int_list("cycles"), &[1, 2, 3]);
+
+        assert!(Params::parse(SPECS, &["rounds".to_string()]).is_err());
+        assert!(Params::parse(SPECS, &["rounds=-1".to_string()]).is_err());
+        assert!(Params::parse(SPECS, &["cycles=1,,2".to_string()]).is_err());
+        assert_eq!(
+            Params::parse(SPECS, &["rounds=0".to_string()]).unwrap_err(),
+            "invalid value for rounds: 0 is out of range, expected 1 to 1000"
+        );
+        assert!(Params::parse(SPECS, &["rounds=1001".to_string()]).is_err());
+        assert!(Params::parse(SPECS, &["cycles=5,0".to_string()])
+            .unwrap_err()
+            .contains("expected at least 1"));
+        assert!(Params::parse(SPECS, &["knots=3".to_string()])
+            .unwrap_err()
+            .contains("expected one of: rounds, cycles"));
+    }
+
+    #[test]
+    fn test_defaults_parse() {
+        for day in Day::all() {
+            Params::default().fill_defaults(day.params());
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use std::fs;
+
+use crate::runner::{self, format_duration, Outcome, RunResult};
+
+const START_MARKER: &str = "<!-- report:start -->";
+const END_MARKER: &str = "<!-- report:end -->";
+
+/// Runs all solutions and rewrites the results table in the README.
+pub fn run(readme_path: &str, mask: bool) {
+    let results = runner::run_all(None, None, false);
+    let table = render_table(&results, mask);
+
+    let readme = fs::read_to_string(readme_path).expect("Read the README");
+    let updated = replace_section(&readme, &table).expect("Replace the results section");
+    fs::write(readme_path, updated).expect("Write the README");
+
+    print!("{}", table);
+    println!("Updated {} with {} results", readme_path, results.len());
+}
+
+fn render_table(results: &[RunResult], mask: bool) -> String {
+    let mut table = String::new();
+    table.push_str("| Day | Part | Answer | Runtime | Source |\n");
+    table.push_str("| --- | ---- | ------ | ------- | ------ |\n");
+    for result in results {
+        let answer = match &result.outcome {
+            Outcome::Answer(answer) if mask => format!("`{}`", mask_answer(answer)),
+            Outcome::Answer(answer) => format!("`{}`", answer),
+            Outcome::NotSolved => "*not solved*".to_string(),
+            Outcome::Panicked(_) => "*panicked*".to_string(),
+            Outcome::TimedOut => "*timed out*".to_string(),
+        };
+        let source = result.day.source_path();
+        table.push_str(&format!(
+            "| {} | {} | {} | {} | [{}]({}) |\n",
+            result.day,
+            result.part,
+            answer,
+            format_duration(result.duration),
+            source,
+            source
+        ));
+    }
+    table
+}
+
+fn mask_answer(answer: &str) -> String {
+    answer
+        .chars()
+        .enumerate()
+        .map(|(i, c)| if i == 0 { c } else { '*' })
+        .collect()
+}
+
+/// Replaces everything between the report markers with `section`, or adds
+/// a new results section at the end if there are no markers yet.
+fn replace_section(readme: &str, section: &str) -> Result<String, String> {
+    let start = match readme.find(START_MARKER) {
+        Some(start) => start + START_MARKER.len(),
+        None => {
+            let mut updated = readme.trim_end().to_string();
+            updated.push_str("\n\n## Results\n\n");
+            updated.push_str(START_MARKER);
+            updated.push_str("\n\n");
+            updated.push_str(section);
+            updated.push('\n');
+            updated.push_str(END_MARKER);
+            updated.push('\n');
+            return Ok(updated);
+        }
+    };
+    let end = readme[start..]
+        .find(END_MARKER)
+        .map(|end| start + end)
+        .ok_or(format!("found {} but no {}", START_MARKER, END_MARKER))?;
+
+    Ok(format!(
+        "{}\n\n{}\n{}",
+        &readme[..start],
+        section,
+        &readme[end..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::report::{mask_answer, replace_section};
+
+    #[test]
+    fn test_mask_answer() {
+        assert_eq!(mask_answer("66306"), "6****");
+        assert_eq!(mask_answer(""), "");
+    }
+
+    #[test]
+    fn test_replace_section_existing() {
+        let readme = "# Title\n<!-- report:start -->\nold\n<!-- report:end -->\nfooter\n";
+        assert_eq!(
+            replace_section(readme, "new\n").unwrap(),
+            "# Title\n<!-- report:start -->\n\nnew\n\n<!-- report:end -->\nfooter\n"
+        );
+    }
+
+    #[test]
+    fn test_replace_section_missing() {
+        assert_eq!(
+            replace_section("# Title\n", "new\n").unwrap(),
+            "# Title\n\n## Results\n\n<!-- report:start -->\n\nnew\n\n<!-- report:end -->\n"
+        );
+    }
+
+    #[test]
+    fn test_replace_section_unterminated() {
+        assert!(replace_section("<!-- report:start -->\n", "new\n").is_err());
+    }
+}
@@ -0,0 +1,352 @@
+use std::any::Any;
+use std::fmt::Display;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::artifact::{Artifact, Artifacts};
+use crate::cache::{self, Cache, Entry};
+use crate::crypt;
+use crate::day::{Context, Day, Part};
+use crate::input::Input;
+
+/// How running a single day and part ended.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Outcome {
+    Answer(String),
+    NotSolved,
+    Panicked(String),
+    TimedOut,
+}
+
+impl Outcome {
+    pub fn answer(&self) -> Option<&str> {
+        match self {
+            Outcome::Answer(answer) => Some(answer),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Answer(answer) => write!(f, "{}", answer),
+            Outcome::NotSolved => write!(f, "not solved"),
+            Outcome::Panicked(message) => write!(f, "panicked: {}", message),
+            Outcome::TimedOut => write!(f, "timed out"),
+        }
+    }
+}
+
+/// The outcome of running a single day and part.
+pub struct RunResult {
+    pub day: Day,
+    pub part: Part,
+    pub outcome: Outcome,
+    pub duration: Duration,
+    /// Whether the answer came from the cache instead of solving.
+    pub cached: bool,
+    pub phases: Option<Phases>,
+    /// What the solution printed, if its output was captured.
+    #[cfg_attr(not(feature = "tui"), allow(dead_code))]
+    pub output: String,
+    /// What the solution emitted, if it was collecting artifacts.
+    pub artifacts: Vec<Artifact>,
+}
+
+/// Time spent parsing the input versus solving, for solutions that mark
+/// the end of parsing with [`Context::parsed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Phases {
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+impl Phases {
+    fn measure(start: Instant, parsed_at: Option<Instant>, end: Instant) -> Option<Phases> {
+        parsed_at.map(|parsed_at| Phases {
+            parse: parsed_at - start,
+            solve: end - parsed_at,
+        })
+    }
+}
+
+impl Display for Phases {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "parse {}, solve {}",
+            format_duration(self.parse),
+            format_duration(self.solve)
+        )
+    }
+}
+
+pub fn run(day: Day, part: Part, ctx: &mut Context) -> RunResult {
+    ctx.parsed_at = None;
+    let start = Instant::now();
+    let answer = day.run(part, ctx);
+    let end = Instant::now();
+    RunResult {
+        day,
+        part,
+        outcome: answer.map_or(Outcome::NotSolved, Outcome::Answer),
+        duration: end - start,
+        cached: false,
+        phases: Phases::measure(start, ctx.parsed_at, end),
+        output: ctx.out.take_captured(),
+        artifacts: ctx.artifacts.take(),
+    }
+}
+
+/// Runs a solution on a worker thread, turning panics into
+/// [`Outcome::Panicked`].
+///
+/// If the solution doesn't finish within `timeout` it's reported as
+/// [`Outcome::TimedOut`]. The worker thread can't be stopped, so it's left
+/// running in the background until the process exits.
+pub fn run_isolated(
+    day: Day,
+    part: Part,
+    mut ctx: Context,
+    timeout: Option<Duration>,
+) -> RunResult {
+    let (tx, rx) = mpsc::channel();
+    let start = Instant::now();
+    thread::Builder::new()
+        .name(format!("{}-{}", day, part))
+        .spawn(move || {
+            let start = Instant::now();
+            let result = panic::catch_unwind(AssertUnwindSafe(|| day.run(part, &mut ctx)));
+            let end = Instant::now();
+            let phases = Phases::measure(start, ctx.parsed_at, end);
+            let output = ctx.out.take_captured();
+            let artifacts = ctx.artifacts.take();
+            // The receiver is gone if we timed out, so nobody cares anymore
+            let _ = tx.send((result, end - start, phases, output, artifacts));
+        })
+        .expect("Spawn worker thread");
+
+    let received = match timeout {
+        Some(timeout) => rx.recv_timeout(timeout),
+        None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    };
+    // Timed on the worker when possible, so spawning it isn't counted
+    let (outcome, duration, phases, output, artifacts) = match received {
+        Ok((Ok(Some(answer)), duration, phases, output, artifacts)) => {
+            (Outcome::Answer(answer), duration, phases, output, artifacts)
+        }
+        Ok((Ok(None), duration, phases, output, artifacts)) => {
+            (Outcome::NotSolved, duration, phases, output, artifacts)
+        }
+        // Whatever got emitted before the panic may help explain it
+        Ok((Err(payload), duration, _, output, artifacts)) => (
+            Outcome::Panicked(panic_message(payload.as_ref())),
+            duration,
+            None,
+            output,
+            artifacts,
+        ),
+        Err(RecvTimeoutError::Timeout) => (
+            Outcome::TimedOut,
+            start.elapsed(),
+            None,
+            String::new(),
+            vec![],
+        ),
+        Err(RecvTimeoutError::Disconnected) => (
+            Outcome::Panicked("worker thread exited without a result".to_string()),
+            start.elapsed(),
+            None,
+            String::new(),
+            vec![],
+        ),
+    };
+    RunResult {
+        day,
+        part,
+        outcome,
+        duration,
+        cached: false,
+        phases,
+        output,
+        artifacts,
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Runs every day and part on its default input, carrying on past any
+/// solution that panics or times out.
+///
+/// With a cache, answers for unchanged solutions and inputs are taken from
+/// it instead of solving, and new answers are added to it. With
+/// `artifacts`, the solutions collect the artifacts they emit, which
+/// cached answers don't have.
+pub fn run_all(
+    timeout: Option<Duration>,
+    mut cache: Option<&mut Cache>,
+    artifacts: bool,
+) -> Vec<RunResult> {
+    with_quiet_panics(|| {
+        let mut results = vec![];
+        for &day in Day::all() {
+            for &part in Part::all() {
+                results.push(run_one(day, part, timeout, cache.as_deref_mut(), artifacts));
+            }
+        }
+        results
+    })
+}
+
+/// Runs a single day and part on its default input, the same way as
+/// [`run_all`].
+pub fn run_one(
+    day: Day,
+    part: Part,
+    timeout: Option<Duration>,
+    cache: Option<&mut Cache>,
+    artifacts: bool,
+) -> RunResult {
+    if let Some(result) = cache.as_deref().and_then(|cache| cached(day, part, cache)) {
+        return result;
+    }
+
+    // A missing input isn't cached, and fails the run as usual
+    let input_hash = input_hash(day);
+    let version = day.version();
+    let mut ctx = Context::captured(Input::File(day.input_path()));
+    if artifacts {
+        ctx.artifacts = Artifacts::collecting();
+    }
+    let result = run_isolated(day, part, ctx, timeout);
+    if let (Some(cache), Some(input_hash), Outcome::Answer(answer)) =
+        (cache, input_hash, &result.outcome)
+    {
+        let entry = Entry {
+            input_hash,
+            version,
+            answer: answer.clone(),
+            duration: result.duration,
+        };
+        cache.insert(day, part, entry);
+    }
+    result
+}
+
+/// Looks up the answer for a day and part in the cache, if neither the
+/// solution nor the input changed since it was cached.
+pub fn cached(day: Day, part: Part, cache: &Cache) -> Option<RunResult> {
+    let entry = cache.get(day, part, input_hash(day)?, &day.version())?;
+    Some(RunResult {
+        day,
+        part,
+        outcome: Outcome::Answer(entry.answer.clone()),
+        duration: entry.duration,
+        cached: true,
+        phases: None,
+        output: String::new(),
+        artifacts: vec![],
+    })
+}
+
+fn input_hash(day: Day) -> Option<u64> {
+    crypt::read_input(&day.input_path())
+        .ok()
+        .map(|b| cache::hash(&b))
+}
+
+/// Stops panics from being printed while running `f`, for when they're
+/// reported in the results instead.
+pub fn with_quiet_panics<T>(f: impl FnOnce() -> T) -> T {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = f();
+    panic::set_hook(default_hook);
+    result
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_micros();
+    if micros < 1000 {
+        format!("{} µs", micros)
+    } else if micros < 1_000_000 {
+        format!("{:.2} ms", micros as f64 / 1000.0)
+    } else {
+        format!("{:.2} s", duration.as_secs_f64())
+    }
+}
+
+/// Parses durations like `500ms`, `10s` or `2m`. Plain numbers are seconds.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let split = s.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("invalid duration: {:?}", s))?;
+    let seconds = match unit {
+        "ms" => number / 1000.0,
+        "" | "s" => number,
+        "m" => number * 60.0,
+        _ => return Err(format!("unknown duration unit: {:?}", unit)),
+    };
+    Duration::try_from_secs_f64(seconds).map_err(|_| format!("duration out of range: {:?}", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    #[cfg(feature = "day04")]
+    use crate::day::{Context, Day, Part};
+    #[cfg(feature = "day04")]
+    use crate::input::Input;
+    use crate::runner::{format_duration, panic_message, parse_duration};
+    #[cfg(feature = "day04")]
+    use crate::runner::{run_isolated, Outcome};
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_micros(12)), "12 µs");
+        assert_eq!(format_duration(Duration::from_micros(1234)), "1.23 ms");
+        assert_eq!(format_duration(Duration::from_millis(2500)), "2.50 s");
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("10s"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_duration("1.5"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("2m"), Ok(Duration::from_secs(120)));
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("10h").is_err());
+        assert!(parse_duration(&format!("{}s", "9".repeat(30))).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "day04")]
+    fn test_run_isolated_panic() {
+        let ctx = Context::captured(Input::Text("1-2,3-4\nnot a range\n".to_string()));
+        let result = run_isolated(Day::Day04, Part::Part1, ctx, None);
+        assert_eq!(
+            result.outcome,
+            Outcome::Panicked("Failed to parse line".to_string())
+        );
+    }
+
+    #[test]
+    fn test_panic_message() {
+        let payload = std::panic::catch_unwind(|| panic!("woa too big diff: {}", 3)).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "woa too big diff: 3");
+    }
+}
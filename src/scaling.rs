@@ -0,0 +1,172 @@
+use std::time::Duration;
+
+use crate::day::{Context, Day, Part};
+use crate::generate::Rng;
+use crate::input::Input;
+use crate::runner::{self, format_duration, Outcome};
+
+/// Runs shorter than this are mostly noise, so they're left out of the fit
+/// if there are enough longer ones.
+const MIN_FIT_DURATION: Duration = Duration::from_micros(200);
+
+/// The fastest of `runs` runs of a part on some generated input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Sample {
+    /// Size of the input in bytes.
+    bytes: usize,
+    duration: Duration,
+}
+
+/// Fits `duration = c * bytes^k` to the samples by least squares on their
+/// logarithms, returning `k`.
+fn fit_exponent(samples: &[Sample]) -> Option<f64> {
+    let long: Vec<&Sample> = samples
+        .iter()
+        .filter(|s| s.duration >= MIN_FIT_DURATION)
+        .collect();
+    let used: Vec<&Sample> = if long.len() >= 3 {
+        long
+    } else {
+        samples.iter().collect()
+    };
+    if used.len() < 2 {
+        return None;
+    }
+
+    let points: Vec<(f64, f64)> = used
+        .iter()
+        .map(|s| {
+            let seconds = s.duration.as_secs_f64().max(1e-9);
+            ((s.bytes as f64).ln(), seconds.ln())
+        })
+        .collect();
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let covariance: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+    let variance: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+    if variance == 0.0 {
+        return None;
+    }
+    Some(covariance / variance)
+}
+
+/// The complexity class a growth exponent most likely comes from. Doubling
+/// sizes can't tell `n` from `n log n` apart, so they share a class.
+fn complexity_class(exponent: f64) -> &'static str {
+    match exponent {
+        k if k < 0.5 => "O(1) or O(log n)",
+        k if k < 1.5 => "O(n) or O(n log n)",
+        k if k < 2.5 => "O(n²)",
+        _ => "O(n³) or worse",
+    }
+}
+
+/// Times each part of a day on generated inputs, doubling the size from
+/// `from` for up to `steps` sizes or until a run takes longer than
+/// `budget`, then reports how the runtime grows with the input size.
+pub fn run(day: Day, from: usize, steps: usize, runs: usize, budget: Duration) {
+    let mut superlinear = 0;
+    runner::with_quiet_panics(|| {
+        for &part in Part::all() {
+            println!(">>> {}, {}", day, part);
+            let mut samples = vec![];
+            let mut size = from;
+            for _ in 0..steps {
+                let text = day
+                    .generate(size, &mut Rng::new(size as u64))
+                    .expect("Day generates inputs");
+                let bytes = text.len();
+                let mut fastest: Option<Duration> = None;
+                for _ in 0..runs {
+                    let ctx = Context::captured(Input::Text(text.clone()));
+                    let result = runner::run_isolated(day, part, ctx, Some(budget * 10));
+                    if !matches!(result.outcome, Outcome::Answer(_)) {
+                        println!(
+                            "    size {:>8}  {}, not trying larger sizes",
+                            size, result.outcome
+                        );
+                        if result.outcome == Outcome::TimedOut {
+                            // The worker can't be stopped
+                            println!("    it keeps running in the background, so later timings may be slower");
+                        }
+                        fastest = None;
+                        break;
+                    }
+                    fastest = Some(fastest.map_or(result.duration, |f| f.min(result.duration)));
+                }
+                let Some(duration) = fastest else {
+                    break;
+                };
+                println!(
+                    "    size {:>8}  {:>10} bytes  {:>10}",
+                    size,
+                    bytes,
+                    format_duration(duration)
+                );
+                samples.push(Sample { bytes, duration });
+                if duration > budget {
+                    break;
+                }
+                size *= 2;
+            }
+
+            match fit_exponent(&samples) {
+                Some(exponent) => {
+                    println!(
+                        "    grows like n^{:.2}, probably {}",
+                        exponent,
+                        complexity_class(exponent)
+                    );
+                    if exponent >= 1.5 {
+                        superlinear += 1;
+                    }
+                }
+                None => println!("    not enough sizes to estimate the growth"),
+            }
+        }
+    });
+
+    if superlinear > 0 {
+        println!(
+            "{} part(s) of {} grow faster than linearly with the input",
+            superlinear, day
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::scaling::{complexity_class, fit_exponent, Sample};
+
+    fn samples(exponent: f64) -> Vec<Sample> {
+        (0..6)
+            .map(|i| {
+                let bytes = 1000 << i;
+                Sample {
+                    bytes,
+                    duration: Duration::from_secs_f64(1e-6 * (bytes as f64).powf(exponent)),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fit_exponent() {
+        for exponent in [1.0, 2.0] {
+            let fitted = fit_exponent(&samples(exponent)).unwrap();
+            assert!((fitted - exponent).abs() < 0.01, "{}", fitted);
+        }
+        assert_eq!(fit_exponent(&samples(1.0)[..1]), None);
+    }
+
+    #[test]
+    fn test_complexity_class() {
+        assert_eq!(complexity_class(0.1), "O(1) or O(log n)");
+        assert_eq!(complexity_class(1.1), "O(n) or O(n log n)");
+        assert_eq!(complexity_class(1.9), "O(n²)");
+        assert_eq!(complexity_class(3.2), "O(n³) or worse");
+    }
+}
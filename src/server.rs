@@ -0,0 +1,156 @@
+use std::time::Duration;
+
+use clap::ValueEnum;
+
+use crate::day::{Context, Day, Part};
+use crate::http::{Request, Response, Server};
+use crate::input::Input;
+use crate::json;
+use crate::runner::{self, Outcome};
+
+/// Serves a JSON API for running solutions on the given port of localhost,
+/// until the process is stopped:
+///
+/// - `GET /days` lists the days and their implementations.
+/// - `POST /days/<day>/<part>` runs a solution on the request body.
+///
+/// Only listens on localhost, since anyone who can reach it can make it
+/// spin up solutions.
+pub fn run(port: u16, timeout: Option<Duration>) {
+    let server = Server::bind(port).expect("Bind the server port");
+    println!("Listening on http://{}", server.local_addr());
+    runner::with_quiet_panics(|| server.run(move |request| handle(request, timeout)));
+}
+
+fn handle(request: &Request, timeout: Option<Duration>) -> Response {
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["days"]) => list_days(),
+        ("POST", ["days", day, part]) => {
+            let Some(day) = Day::from_str(day, true).ok().filter(Day::is_compiled) else {
+                return Response::error(404, &format!("unknown day: {}", day));
+            };
+            let Ok(part) = Part::from_str(part, true) else {
+                return Response::error(404, &format!("unknown part: {}", part));
+            };
+            solve(day, part, &request.body, timeout)
+        }
+        (_, ["days"]) | (_, ["days", _, _]) => Response::error(405, "method not allowed"),
+        _ => Response::error(404, "not found"),
+    }
+}
+
+fn list_days() -> Response {
+    let days: Vec<String> = Day::all()
+        .iter()
+        .map(|day| {
+            let impls: Vec<String> = day.impls().iter().map(|i| json::string(i)).collect();
+            format!(
+                "{{\"day\":{},\"impls\":[{}]}}",
+                json::string(&day.to_string()),
+                impls.join(",")
+            )
+        })
+        .collect();
+    Response::new(200, format!("{{\"days\":[{}]}}", days.join(",")))
+}
+
+fn solve(day: Day, part: Part, input: &str, timeout: Option<Duration>) -> Response {
+    let ctx = Context::captured(Input::Text(input.to_string()));
+    let result = runner::run_isolated(day, part, ctx, timeout);
+    let (status, error) = match &result.outcome {
+        Outcome::Answer(_) => (200, None),
+        Outcome::NotSolved => (200, Some("not solved".to_string())),
+        Outcome::Panicked(message) => (422, Some(message.clone())),
+        Outcome::TimedOut => (504, Some("timed out".to_string())),
+    };
+    Response::new(
+        status,
+        format!(
+            "{{\"day\":{},\"part\":{},\"answer\":{},\"duration_us\":{},\"parse_us\":{},\"solve_us\":{},\"error\":{}}}",
+            json::string(&day.to_string()),
+            json::string(&part.to_string()),
+            json::optional_string(result.outcome.answer()),
+            result.duration.as_micros(),
+            json::optional_number(result.phases.map(|p| p.parse.as_micros())),
+            json::optional_number(result.phases.map(|p| p.solve.as_micros())),
+            json::optional_string(error.as_deref())
+        ),
+    )
+}
+
+#[cfg(all(test, feature = "day04"))]
+mod tests {
+    use std::io::prelude::*;
+    use std::net::TcpStream;
+    use std::thread;
+
+    #[cfg(feature = "day06")]
+    use crate::http::Request;
+    use crate::http::Server;
+    use crate::server::handle;
+
+    #[cfg(feature = "day06")]
+    fn request(method: &str, path: &str, body: &str) -> Request {
+        Request {
+            method: method.to_string(),
+            path: path.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "day06")]
+    fn test_handle() {
+        let response = handle(&request("GET", "/days", ""), None);
+        assert_eq!(response.status, 200);
+        assert!(response
+            .body
+            .contains("{\"day\":\"day06\",\"impls\":[\"naive\",\"bitmask\"]}"));
+
+        let response = handle(
+            &request("POST", "/days/day04/part1", "2-4,6-8\n2-8,3-7\n"),
+            None,
+        );
+        assert_eq!(response.status, 200);
+        assert!(response.body.contains("\"answer\":\"1\""));
+        assert!(response.body.contains("\"error\":null"));
+
+        let response = handle(&request("POST", "/days/day04/part1", "nope\n"), None);
+        assert_eq!(response.status, 422);
+        assert!(response.body.contains("\"answer\":null"));
+        assert!(response.body.contains("\"error\":\"Failed to parse line\""));
+
+        assert_eq!(
+            handle(&request("POST", "/days/day99/part1", ""), None).status,
+            404
+        );
+        assert_eq!(
+            handle(&request("GET", "/days/day01/part1", ""), None).status,
+            405
+        );
+        assert_eq!(handle(&request("GET", "/", ""), None).status, 404);
+    }
+
+    #[test]
+    fn test_serve() {
+        let server = Server::bind(0).unwrap();
+        let addr = server.local_addr();
+        assert!(addr.ip().is_loopback());
+        thread::spawn(move || server.run(|request| handle(request, None)));
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        let body = "2-4,6-8\n2-8,3-7\n6-6,4-6\n";
+        write!(
+            stream,
+            "POST /days/day04/part1 HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("\"answer\":\"2\""));
+    }
+}
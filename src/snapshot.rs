@@ -0,0 +1,101 @@
+use std::fs;
+use std::path::PathBuf;
+
+/// Where `tests/snapshots.rs` keeps the expected output of each day and
+/// part, with changed output written next to it as `.snap.new`.
+pub const SNAPSHOTS_DIR: &str = "tests/snapshots";
+
+/// Shows the diff of every changed snapshot, and with `accept` replaces the
+/// snapshots with the new output.
+pub fn run(accept: bool) {
+    let pending = pending_snapshots();
+    if pending.is_empty() {
+        println!("No changed snapshots, run `cargo test` first");
+        return;
+    }
+
+    for new_path in &pending {
+        let path = new_path.with_extension("");
+        let old = fs::read_to_string(&path).unwrap_or_default();
+        let new = fs::read_to_string(new_path).expect("Read the new snapshot");
+        println!(">>> {}", path.display());
+        for line in diff(&old, &new) {
+            println!("{}", line);
+        }
+        if accept {
+            fs::rename(new_path, &path).expect("Accept the new snapshot");
+        }
+    }
+
+    if accept {
+        println!("Accepted {} snapshot(s)", pending.len());
+    } else {
+        println!(
+            "{} changed snapshot(s), accept them with `snapshots --accept`",
+            pending.len()
+        );
+    }
+}
+
+fn pending_snapshots() -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(SNAPSHOTS_DIR) else {
+        return vec![];
+    };
+    let mut pending: Vec<PathBuf> = entries
+        .map(|entry| entry.expect("Read the snapshots dir").path())
+        .filter(|path| path.to_string_lossy().ends_with(".snap.new"))
+        .collect();
+    pending.sort();
+    pending
+}
+
+/// Diffs two texts line by line, marking removed lines with `-`, added ones
+/// with `+` and unchanged ones with a space.
+fn diff(old: &str, new: &str) -> Vec<String> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j] is the longest common subsequence of old[i..] and new[j..]
+    let mut lcs = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push(format!("  {}", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(format!("- {}", old[i]));
+            i += 1;
+        } else {
+            lines.push(format!("+ {}", new[j]));
+            j += 1;
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::snapshot::diff;
+
+    #[test]
+    fn test_diff() {
+        assert_eq!(
+            diff("a\nb\nc\n", "a\nx\nc\nd\n"),
+            vec!["  a", "- b", "+ x", "  c", "+ d"]
+        );
+        assert_eq!(diff("", "a\n"), vec!["+ a"]);
+        assert_eq!(diff("a\n", "a\n"), vec!["  a"]);
+    }
+}
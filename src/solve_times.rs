@@ -0,0 +1,351 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+
+use clap::ValueEnum;
+
+use crate::day::{Day, Part};
+use crate::examples;
+use crate::runner::RunResult;
+use crate::time::{self, format_date, format_elapsed};
+use crate::verify::Answers;
+
+/// Where `start`, `done` and `verify` keep track of when each part was
+/// started and solved, one event per line.
+pub const SOLVE_TIMES_PATH: &str = "solve-times.txt";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Event {
+    Start,
+    /// Marked done with the `done` command.
+    Done(Part),
+    /// The first time `verify` saw the right answer.
+    Verified(Part),
+}
+
+/// Something that happened to a day, at some Unix timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Entry {
+    timestamp: u64,
+    day: Day,
+    event: Event,
+}
+
+impl Entry {
+    fn to_line(self) -> String {
+        let event = match self.event {
+            Event::Start => "start".to_string(),
+            Event::Done(part) => format!("done {}", part),
+            Event::Verified(part) => format!("verified {}", part),
+        };
+        format!("{} {} {}", self.timestamp, self.day, event)
+    }
+
+    /// Parses a line, or returns `None` for a day this version doesn't
+    /// know about, like one a newer checkout started.
+    fn from_line(line: &str) -> Result<Option<Entry>, String> {
+        let fields: Vec<&str> = line.split(' ').collect();
+        let (timestamp, day, event) = match fields[..] {
+            [timestamp, day, "start"] => (timestamp, day, Event::Start),
+            [timestamp, day, "done", part] => {
+                (timestamp, day, Event::Done(Part::from_str(part, true)?))
+            }
+            [timestamp, day, "verified", part] => {
+                (timestamp, day, Event::Verified(Part::from_str(part, true)?))
+            }
+            _ => {
+                return Err(format!(
+                    "expected \"<timestamp> <day> <event>\", got {:?}",
+                    line
+                ))
+            }
+        };
+        let timestamp = timestamp
+            .parse()
+            .map_err(|_| format!("invalid timestamp {:?}", timestamp))?;
+        let day = match Day::from_str(day, true) {
+            Ok(day) => day,
+            Err(_) => {
+                examples::parse_day(day)?;
+                return Ok(None);
+            }
+        };
+        Ok(Some(Entry {
+            timestamp,
+            day,
+            event,
+        }))
+    }
+}
+
+fn parse(s: &str) -> Result<Vec<Entry>, String> {
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .filter_map(|(i, line)| {
+            Entry::from_line(line)
+                .map_err(|e| format!("line {}: {}", i + 1, e))
+                .transpose()
+        })
+        .collect()
+}
+
+fn read() -> Vec<Entry> {
+    let s = match fs::read_to_string(SOLVE_TIMES_PATH) {
+        Ok(s) => s,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return vec![],
+        Err(e) => {
+            eprintln!("Couldn't read {}: {}", SOLVE_TIMES_PATH, e);
+            std::process::exit(1);
+        }
+    };
+    parse(&s).unwrap_or_else(|e| {
+        eprintln!("Couldn't parse {}: {}", SOLVE_TIMES_PATH, e);
+        std::process::exit(1);
+    })
+}
+
+fn append(entries: &[Entry]) {
+    let written = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(SOLVE_TIMES_PATH)
+        .and_then(|mut file| {
+            entries
+                .iter()
+                .try_for_each(|entry| writeln!(file, "{}", entry.to_line()))
+        });
+    if let Err(e) = written {
+        eprintln!("Couldn't write to {}: {}", SOLVE_TIMES_PATH, e);
+        std::process::exit(1);
+    }
+}
+
+/// The first time each event happened for a day.
+fn first(entries: &[Entry], day: Day, event: Event) -> Option<u64> {
+    entries
+        .iter()
+        .filter(|e| e.day == day && e.event == event)
+        .map(|e| e.timestamp)
+        .min()
+}
+
+/// How long a part took from starting the day, and whether that's from
+/// `verify` rather than marking it done.
+fn solve_time(entries: &[Entry], day: Day, part: Part) -> Option<(u64, bool)> {
+    let started = first(entries, day, Event::Start)?;
+    let (solved, verified) = match first(entries, day, Event::Done(part)) {
+        Some(done) => (done, false),
+        None => (first(entries, day, Event::Verified(part))?, true),
+    };
+    Some((solved.saturating_sub(started), verified))
+}
+
+/// Starts the clock on a day.
+pub fn start(day: Day) {
+    let entries = read();
+    if let Some(started) = first(&entries, day, Event::Start) {
+        println!(
+            "{} was already started on {}, keeping that",
+            day,
+            format_date(started)
+        );
+        return;
+    }
+    append(&[Entry {
+        timestamp: time::now(),
+        day,
+        event: Event::Start,
+    }]);
+    println!("Started {}, good luck!", day);
+}
+
+/// Marks a part as solved now.
+pub fn done(day: Day, part: Part) {
+    let entries = read();
+    let Some(started) = first(&entries, day, Event::Start) else {
+        eprintln!("{} isn't started, run `start {}` first", day, day);
+        std::process::exit(1);
+    };
+    if first(&entries, day, Event::Done(part)).is_some() {
+        println!("{} {} is already done", day, part);
+        return;
+    }
+    let now = time::now();
+    append(&[Entry {
+        timestamp: now,
+        day,
+        event: Event::Done(part),
+    }]);
+    println!(
+        "{} {} done in {}",
+        day,
+        part,
+        format_elapsed(now.saturating_sub(started) as i64)
+    );
+}
+
+/// Records the first time `verify` sees the right answer for a part of a
+/// started day, which counts as solving it unless it was marked done.
+pub fn record_verified(results: &[RunResult], answers: &Answers) {
+    let entries = read();
+    let now = time::now();
+    let mut verified = vec![];
+    for result in results {
+        let correct = result
+            .outcome
+            .answer()
+            .is_some_and(|answer| answers.get(result.day, result.part) == Some(answer));
+        let event = Event::Verified(result.part);
+        if !correct
+            || first(&entries, result.day, Event::Start).is_none()
+            || first(&entries, result.day, event).is_some()
+        {
+            continue;
+        }
+        verified.push(Entry {
+            timestamp: now,
+            day: result.day,
+            event,
+        });
+    }
+    if verified.is_empty() {
+        return;
+    }
+    append(&verified);
+    for entry in &verified {
+        let started = first(&entries, entry.day, Event::Start).unwrap_or(entry.timestamp);
+        if let Event::Verified(part) = entry.event {
+            println!(
+                "First correct answer for {} {}, {} after starting",
+                entry.day,
+                part,
+                format_elapsed(entry.timestamp.saturating_sub(started) as i64)
+            );
+        }
+    }
+}
+
+fn table(entries: &[Entry]) -> Vec<String> {
+    // The last character of each time is left for the `*`
+    let mut lines = vec![format!(
+        "{:<6} {:<10}  {:>9}  {:>9}",
+        "", "started", "part1", "part2"
+    )];
+    let mut from_verify = false;
+    for &day in Day::value_variants() {
+        let Some(started) = first(entries, day, Event::Start) else {
+            continue;
+        };
+        let cells: Vec<String> = Part::all()
+            .iter()
+            .map(|&part| match solve_time(entries, day, part) {
+                Some((secs, verified)) => {
+                    from_verify |= verified;
+                    format!(
+                        "{}{}",
+                        format_elapsed(secs as i64),
+                        if verified { "*" } else { " " }
+                    )
+                }
+                None => "- ".to_string(),
+            })
+            .collect();
+        lines.push(format!(
+            "{:<6} {:<10}  {:>10} {:>10}",
+            day.to_string(),
+            format_date(started),
+            cells[0],
+            cells[1]
+        ));
+    }
+    if from_verify {
+        lines.push("* not marked done, timed to the first correct answer in verify".to_string());
+    }
+    lines
+}
+
+/// Prints how long each part of each started day took to solve.
+pub fn stats() {
+    let entries = read();
+    if entries.is_empty() {
+        println!("No solve times yet, start a day with `start dayNN`");
+        return;
+    }
+    for line in table(&entries) {
+        println!("{}", line.trim_end());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day::{Day, Part};
+    use crate::solve_times::{parse, solve_time, table, Entry, Event};
+
+    const LOG: &str = "\
+1670389200 day07 start
+1670390590 day07 done part1
+1670391000 day07 verified part1
+1670392700 day07 verified part2
+1670389300 day08 start
+";
+
+    #[test]
+    fn test_parse() {
+        let entries = parse(LOG).unwrap();
+        assert_eq!(
+            entries[1],
+            Entry {
+                timestamp: 1670390590,
+                day: Day::Day07,
+                event: Event::Done(Part::Part1)
+            }
+        );
+        let lines: Vec<String> = entries.iter().map(|e| e.to_line()).collect();
+        assert_eq!(lines.join("\n") + "\n", LOG);
+        assert!(parse("1 day07 finished\n").is_err());
+        assert!(parse("soon day07 start\n").is_err());
+        assert!(parse("1 dayy07 start\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_unknown_day() {
+        let entries = parse("1 day24 start\n2 day07 start\n3 day24 done part1\n").unwrap();
+        assert_eq!(
+            entries,
+            vec![Entry {
+                timestamp: 2,
+                day: Day::Day07,
+                event: Event::Start
+            }]
+        );
+    }
+
+    #[test]
+    fn test_solve_time() {
+        let entries = parse(LOG).unwrap();
+        assert_eq!(
+            solve_time(&entries, Day::Day07, Part::Part1),
+            Some((1390, false))
+        );
+        assert_eq!(
+            solve_time(&entries, Day::Day07, Part::Part2),
+            Some((3500, true))
+        );
+        assert_eq!(solve_time(&entries, Day::Day08, Part::Part1), None);
+        assert_eq!(solve_time(&entries, Day::Day01, Part::Part1), None);
+    }
+
+    #[test]
+    fn test_table() {
+        let entries = parse(LOG).unwrap();
+        assert_eq!(
+            table(&entries),
+            vec![
+                "       started         part1      part2",
+                "day07  2022-12-07    0:23:10    0:58:20*",
+                "day08  2022-12-07          -          - ",
+                "* not marked done, timed to the first correct answer in verify",
+            ]
+        );
+    }
+}
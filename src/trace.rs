@@ -0,0 +1,50 @@
+use std::fmt;
+
+use crate::day::Output;
+
+/// Explains how a solution gets to its answer one step at a time, shown
+/// with `--explain`.
+///
+/// A disabled trace ignores all steps, so solutions can call it
+/// unconditionally. Adding steps through [`explain!`] also skips formatting
+/// them when disabled.
+pub struct Trace {
+    out: Option<Output>,
+}
+
+impl Trace {
+    pub fn new(out: Output) -> Self {
+        Self { out: Some(out) }
+    }
+
+    pub fn disabled() -> Self {
+        Self { out: None }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.out.is_some()
+    }
+
+    pub fn step(&mut self, args: fmt::Arguments) {
+        if let Some(out) = &mut self.out {
+            writeln!(out, "{}", args);
+        }
+    }
+
+    /// Takes the steps captured so far, see [`Output::take_captured`].
+    #[cfg(test)]
+    pub fn take_captured(&mut self) -> String {
+        self.out.as_mut().map_or(String::new(), Output::take_captured)
+    }
+}
+
+/// Adds a step to a [`Trace`], formatted like `println!`.
+macro_rules! explain {
+    ($trace:expr, $($arg:tt)*) => {
+        if $trace.is_enabled() {
+            $trace.step(format_args!($($arg)*));
+        }
+    };
+}
+
+pub(crate) use explain;
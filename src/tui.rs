@@ -0,0 +1,511 @@
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::style::Attribute;
+use crossterm::{cursor, execute, queue, terminal};
+
+use crate::cache::{Cache, CACHE_PATH};
+use crate::check;
+use crate::crypt;
+use crate::day::{Day, Part};
+use crate::runner::{self, format_duration, Outcome, RunResult};
+use crate::verify::{Answers, ANSWERS_PATH};
+
+/// Lines above the first day in the table view.
+const HEADER_LINES: usize = 2;
+
+/// How many lines of the input to show in its summary.
+const PREVIEW_LINES: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum View {
+    Table,
+    Input,
+    Log,
+}
+
+struct Row {
+    day: Day,
+    part: Part,
+    result: Option<RunResult>,
+}
+
+/// The state of the dashboard, kept apart from the terminal so it can be
+/// rendered in tests.
+struct Dashboard {
+    rows: Vec<Row>,
+    selected: usize,
+    view: View,
+    scroll: usize,
+    answers: Option<Answers>,
+    cache: Cache,
+    timeout: Duration,
+    status: String,
+}
+
+impl Dashboard {
+    fn new(answers: Option<Answers>, cache: Cache, timeout: Duration) -> Dashboard {
+        let mut rows = vec![];
+        for &day in Day::all() {
+            for &part in Part::all() {
+                let result = runner::cached(day, part, &cache);
+                rows.push(Row { day, part, result });
+            }
+        }
+        Dashboard {
+            rows,
+            selected: 0,
+            view: View::Table,
+            scroll: 0,
+            answers,
+            cache,
+            timeout,
+            status: String::new(),
+        }
+    }
+
+    /// Runs a row, sharing the answer cache with `all` and `verify`. With
+    /// `fresh`, a cached answer is solved again.
+    ///
+    /// The dashboard waits for the run, but never longer than the timeout.
+    fn run(&mut self, index: usize, fresh: bool) {
+        let (day, part) = (self.rows[index].day, self.rows[index].part);
+        if fresh {
+            self.cache.remove(day, part);
+        }
+        let result = runner::with_quiet_panics(|| {
+            runner::run_one(day, part, Some(self.timeout), Some(&mut self.cache), false)
+        });
+        self.rows[index].result = Some(result);
+        self.status = match self.cache.save(CACHE_PATH) {
+            Ok(()) => format!("Ran {} {}", day, part),
+            Err(e) => format!("Couldn't save the answer cache to {}: {}", CACHE_PATH, e),
+        };
+    }
+
+    fn selected_row(&self) -> &Row {
+        &self.rows[self.selected]
+    }
+
+    fn status_of(&self, row: &Row) -> &'static str {
+        match &row.result {
+            None => "not run",
+            Some(result) => match &result.outcome {
+                Outcome::Answer(_) if result.cached => "cached",
+                Outcome::Answer(_) => "solved",
+                Outcome::NotSolved => "not solved",
+                Outcome::Panicked(_) => "PANICKED",
+                Outcome::TimedOut => "TIMED OUT",
+            },
+        }
+    }
+
+    fn verified(&self, row: &Row) -> &'static str {
+        let answer = row.result.as_ref().and_then(|r| r.outcome.answer());
+        let expected = self.answers.as_ref().and_then(|a| a.get(row.day, row.part));
+        match (answer, expected) {
+            (None, _) => "",
+            (Some(_), None) => "-",
+            (Some(answer), Some(expected)) if answer == expected => "yes",
+            (Some(_), Some(_)) => "WRONG",
+        }
+    }
+
+    /// Renders the current view as plain lines, cut to fit the terminal.
+    fn render(&self, width: usize, height: usize) -> Vec<String> {
+        let (mut lines, footer) = match self.view {
+            View::Table => (
+                self.render_table(height),
+                "↑/↓ select  r run  R re-run  a run all  i input  l log  q quit",
+            ),
+            View::Input => (
+                self.scrolled(self.render_input()),
+                "↑/↓ scroll  esc back  q quit",
+            ),
+            View::Log => (
+                self.scrolled(self.render_log()),
+                "↑/↓ scroll  esc back  q quit",
+            ),
+        };
+        lines.truncate(height.saturating_sub(2));
+        lines.push(self.status.clone());
+        lines.push(footer.to_string());
+        lines
+            .into_iter()
+            .map(|line| line.chars().take(width).collect())
+            .collect()
+    }
+
+    /// How many days the table is scrolled down by, so that the selected
+    /// one fits above the status and footer lines.
+    fn table_offset(&self, height: usize) -> usize {
+        let visible = height.saturating_sub(HEADER_LINES + 2).max(1);
+        (self.selected + 1).saturating_sub(visible)
+    }
+
+    fn render_table(&self, height: usize) -> Vec<String> {
+        let mut lines = vec![
+            "Advent of Code 2022".to_string(),
+            format!(
+                "{:<6} {:<6} {:<11} {:<20} {:<8} {:>10}",
+                "Day", "Part", "Status", "Answer", "Verified", "Runtime"
+            ),
+        ];
+        if self.rows.is_empty() {
+            lines.push("No days compiled in, rebuild with --features all-days".to_string());
+        }
+        for row in self.rows.iter().skip(self.table_offset(height)) {
+            let runtime = match &row.result {
+                None => String::new(),
+                Some(result) if result.cached => "cached".to_string(),
+                Some(result) => format_duration(result.duration),
+            };
+            lines.push(format!(
+                "{:<6} {:<6} {:<11} {:<20} {:<8} {:>10}",
+                row.day.to_string(),
+                row.part.to_string(),
+                self.status_of(row),
+                row.result
+                    .as_ref()
+                    .and_then(|r| r.outcome.answer())
+                    .unwrap_or(""),
+                self.verified(row),
+                runtime
+            ));
+        }
+        lines
+    }
+
+    fn render_input(&self) -> Vec<String> {
+        let day = self.selected_row().day;
+        let path = day.input_path();
+        let mut lines = vec![format!("Input of {}: {}", day, path)];
+        let bytes = match crypt::read_input(&path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                lines.push(e);
+                return lines;
+            }
+        };
+        let text = String::from_utf8_lossy(&bytes);
+        lines.push(format!(
+            "{} bytes, {} lines",
+            bytes.len(),
+            text.lines().count()
+        ));
+
+        let problems = check::check_bytes(day, &bytes);
+        if problems.is_empty() {
+            lines.push("No problems found".to_string());
+        } else {
+            lines.push(format!("{} problem(s):", problems.len()));
+            lines.extend(problems.iter().map(|p| format!("  {}", p)));
+        }
+
+        lines.push(String::new());
+        lines.extend(text.lines().take(PREVIEW_LINES).map(|l| l.to_string()));
+        lines
+    }
+
+    fn render_log(&self) -> Vec<String> {
+        let row = self.selected_row();
+        let mut lines = vec![format!("Output of {} {}", row.day, row.part)];
+        match &row.result {
+            None => lines.push("Not run yet, press r in the table to run it".to_string()),
+            Some(result) if result.cached => {
+                lines.push("The answer came from the cache, press R to re-run it".to_string())
+            }
+            Some(result) => {
+                lines.extend(result.output.lines().map(|l| l.to_string()));
+                if let Outcome::Panicked(message) = &result.outcome {
+                    lines.push(format!("panicked: {}", message));
+                }
+            }
+        }
+        lines
+    }
+
+    /// Keeps the title line in place while scrolling the rest.
+    fn scrolled(&self, mut lines: Vec<String>) -> Vec<String> {
+        let skip = self.scroll.min(lines.len().saturating_sub(2));
+        lines.drain(1..1 + skip);
+        lines
+    }
+
+    /// Handles a key press, returning false to quit.
+    ///
+    /// `draw` shows progress while running, and returns false when the user
+    /// asks to stop running everything.
+    fn key(&mut self, code: KeyCode, draw: &mut impl FnMut(&Dashboard) -> bool) -> bool {
+        // There's nothing to select or run
+        if self.rows.is_empty() {
+            return code != KeyCode::Char('q');
+        }
+        match (self.view, code) {
+            (_, KeyCode::Char('q')) => return false,
+            (View::Table, KeyCode::Up | KeyCode::Char('k')) => {
+                self.selected = self.selected.saturating_sub(1)
+            }
+            (View::Table, KeyCode::Down | KeyCode::Char('j')) => {
+                self.selected = (self.selected + 1).min(self.rows.len() - 1)
+            }
+            (View::Table, KeyCode::Enter | KeyCode::Char('r' | 'R')) => {
+                let row = self.selected_row();
+                self.status = format!("Running {} {}...", row.day, row.part);
+                draw(self);
+                self.run(self.selected, code == KeyCode::Char('R'));
+            }
+            (View::Table, KeyCode::Char('a')) => {
+                for index in 0..self.rows.len() {
+                    self.selected = index;
+                    self.status = format!(
+                        "Running {} {}... (esc to stop)",
+                        self.rows[index].day, self.rows[index].part
+                    );
+                    if !draw(self) {
+                        self.status = "Stopped running everything".to_string();
+                        return true;
+                    }
+                    self.run(index, false);
+                }
+                self.status = "Ran everything".to_string();
+            }
+            (View::Table, KeyCode::Char('i')) => self.open(View::Input),
+            (View::Table, KeyCode::Char('l')) => self.open(View::Log),
+            (_, KeyCode::Esc | KeyCode::Backspace) => self.view = View::Table,
+            (_, KeyCode::Up | KeyCode::Char('k')) => self.scroll = self.scroll.saturating_sub(1),
+            (_, KeyCode::Down | KeyCode::Char('j')) => self.scroll += 1,
+            _ => (),
+        }
+        true
+    }
+
+    fn open(&mut self, view: View) {
+        self.view = view;
+        self.scroll = 0;
+    }
+}
+
+/// Puts the terminal in raw mode on the alternate screen, restoring it
+/// when dropped, even if we panic.
+struct Terminal;
+
+impl Terminal {
+    fn enter() -> io::Result<Terminal> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(Terminal)
+    }
+
+    fn draw(&self, dashboard: &Dashboard) -> io::Result<()> {
+        let (width, height) = terminal::size()?;
+        let lines = dashboard.render(width as usize, height as usize);
+        let highlighted = (dashboard.view == View::Table && !dashboard.rows.is_empty())
+            .then(|| dashboard.selected - dashboard.table_offset(height as usize) + HEADER_LINES);
+
+        let mut stdout = io::stdout().lock();
+        queue!(
+            stdout,
+            cursor::MoveTo(0, 0),
+            terminal::Clear(terminal::ClearType::All)
+        )?;
+        for (i, line) in lines.iter().enumerate() {
+            if i > 0 {
+                write!(stdout, "\r\n")?;
+            }
+            if highlighted == Some(i) {
+                write!(
+                    stdout,
+                    "{}{:<width$}{}",
+                    Attribute::Reverse,
+                    line,
+                    Attribute::Reset,
+                    width = width as usize
+                )?;
+            } else {
+                write!(stdout, "{}", line)?;
+            }
+        }
+        stdout.flush()
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Shows a full-screen dashboard of every day and part, for running them
+/// and looking at their inputs and output.
+pub fn run(timeout: Duration) {
+    let answers = Path::new(ANSWERS_PATH)
+        .exists()
+        .then(|| Answers::read(ANSWERS_PATH));
+    let mut dashboard = Dashboard::new(answers, Cache::load(CACHE_PATH), timeout);
+    if let Err(e) = event_loop(&mut dashboard) {
+        eprintln!("Terminal error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn event_loop(dashboard: &mut Dashboard) -> io::Result<()> {
+    let terminal = Terminal::enter()?;
+    loop {
+        terminal.draw(dashboard)?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind == KeyEventKind::Release {
+            continue;
+        }
+        let mut draw = |dashboard: &Dashboard| {
+            let _ = terminal.draw(dashboard);
+            !stop_requested()
+        };
+        if !dashboard.key(key.code, &mut draw) {
+            return Ok(());
+        }
+    }
+}
+
+/// Whether esc or q was pressed since the last check, dropping any other
+/// keys pressed in the meantime.
+fn stop_requested() -> bool {
+    while event::poll(Duration::ZERO).unwrap_or(false) {
+        if let Ok(Event::Key(key)) = event::read() {
+            if key.kind != KeyEventKind::Release
+                && matches!(key.code, KeyCode::Esc | KeyCode::Char('q'))
+            {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use clap::ValueEnum;
+    use crossterm::event::KeyCode;
+
+    use crate::cache::Cache;
+    use crate::day::{Day, Part};
+    use crate::runner::{Outcome, RunResult};
+    use crate::tui::{Dashboard, Row, View, HEADER_LINES};
+    use crate::verify::Answers;
+
+    fn result(outcome: Outcome, output: &str) -> RunResult {
+        RunResult {
+            day: Day::Day01,
+            part: Part::Part1,
+            outcome,
+            duration: Duration::from_micros(1500),
+            cached: false,
+            phases: None,
+            output: output.to_string(),
+            artifacts: vec![],
+        }
+    }
+
+    /// A dashboard with every day, whether or not it's compiled in.
+    fn dashboard() -> Dashboard {
+        let answers = Answers::read("inputs/answers.txt");
+        let mut dashboard = Dashboard::new(Some(answers), Cache::default(), Duration::from_secs(1));
+        dashboard.rows = Day::value_variants()
+            .iter()
+            .flat_map(|&day| {
+                Part::all().iter().map(move |&part| Row {
+                    day,
+                    part,
+                    result: None,
+                })
+            })
+            .collect();
+        dashboard
+    }
+
+    #[test]
+    fn test_render_table() {
+        let mut dashboard = dashboard();
+        dashboard.rows[0].result = Some(result(Outcome::Answer("1".to_string()), ""));
+        dashboard.rows[1].result = Some(result(Outcome::Panicked("oops".to_string()), ""));
+
+        let lines = dashboard.render(80, 40);
+        assert_eq!(
+            lines[HEADER_LINES],
+            "day01  part1  solved      1                    WRONG       1.50 ms"
+        );
+        assert!(lines[HEADER_LINES + 1].starts_with("day01  part2  PANICKED"));
+        assert!(lines[HEADER_LINES + 2].starts_with("day02  part1  not run"));
+
+        let lines = dashboard.render(10, 5);
+        assert_eq!(lines.len(), 5);
+        assert!(lines.iter().all(|l| l.chars().count() <= 10));
+    }
+
+    #[test]
+    fn test_scroll_table() {
+        let mut dashboard = dashboard();
+        // Room for 3 days
+        let height = HEADER_LINES + 3 + 2;
+        dashboard.selected = 2;
+        assert_eq!(dashboard.table_offset(height), 0);
+        dashboard.selected = 5;
+        assert_eq!(dashboard.table_offset(height), 3);
+        let lines = dashboard.render(80, height);
+        assert!(lines[HEADER_LINES + 2].starts_with(&format!(
+            "{:<6} {:<6}",
+            dashboard.rows[5].day.to_string(),
+            dashboard.rows[5].part.to_string()
+        )));
+    }
+
+    #[test]
+    fn test_keys() {
+        let mut dashboard = dashboard();
+        dashboard.rows[0].result = Some(result(Outcome::Panicked("oops".to_string()), "line\n"));
+        let mut draw = |_: &Dashboard| true;
+
+        assert!(dashboard.key(KeyCode::Up, &mut draw));
+        assert_eq!(dashboard.selected, 0);
+        dashboard.key(KeyCode::Char('l'), &mut draw);
+        assert_eq!(dashboard.view, View::Log);
+        let lines = dashboard.render(80, 40);
+        assert_eq!(
+            &lines[..3],
+            ["Output of day01 part1", "line", "panicked: oops"]
+        );
+
+        dashboard.key(KeyCode::Esc, &mut draw);
+        dashboard.key(KeyCode::Down, &mut draw);
+        assert_eq!((dashboard.view, dashboard.selected), (View::Table, 1));
+        assert!(!dashboard.key(KeyCode::Char('q'), &mut draw));
+    }
+
+    #[test]
+    fn test_no_days() {
+        let mut dashboard = dashboard();
+        dashboard.rows.clear();
+        let mut draw = |_: &Dashboard| true;
+        for code in [
+            KeyCode::Down,
+            KeyCode::Up,
+            KeyCode::Char('r'),
+            KeyCode::Char('l'),
+        ] {
+            assert!(dashboard.key(code, &mut draw));
+        }
+        assert_eq!((dashboard.view, dashboard.selected), (View::Table, 0));
+        assert_eq!(
+            dashboard.render(80, 10)[HEADER_LINES],
+            "No days compiled in, rebuild with --features all-days"
+        );
+        assert!(!dashboard.key(KeyCode::Char('q'), &mut draw));
+    }
+}
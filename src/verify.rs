@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+use std::fs;
+use std::time::Duration;
+
+use clap::ValueEnum;
+
+use crate::artifact;
+use crate::cache::{Cache, CACHE_PATH};
+use crate::day::{Day, Part};
+use crate::external::{self, ExternalResult, SOLVERS_PATH};
+use crate::junit;
+use crate::runner::{self, format_duration, Outcome, RunResult};
+use crate::solve_times;
+
+pub const ANSWERS_PATH: &str = "inputs/answers.txt";
+
+/// Expected answers for our inputs, read from lines like `day01 part1 66306`.
+pub struct Answers(HashMap<(Day, Part), String>);
+
+impl Answers {
+    pub fn read(path: &str) -> Answers {
+        let s = fs::read_to_string(path).expect("Read the answers file");
+        Answers::parse(&s).expect("Parse the answers file")
+    }
+
+    pub fn parse(s: &str) -> Result<Answers, String> {
+        let mut answers = HashMap::new();
+        for (i, line) in s.lines().enumerate() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.splitn(3, ' ');
+            let (day, part, answer) = match (fields.next(), fields.next(), fields.next()) {
+                (Some(day), Some(part), Some(answer)) => (day, part, answer),
+                _ => return Err(format!("line {}: expected \"<day> <part> <answer>\"", i + 1)),
+            };
+            let day = Day::from_str(day, true).map_err(|e| format!("line {}: {}", i + 1, e))?;
+            let part = Part::from_str(part, true).map_err(|e| format!("line {}: {}", i + 1, e))?;
+            answers.insert((day, part), answer.to_string());
+        }
+        Ok(Answers(answers))
+    }
+
+    pub fn get(&self, day: Day, part: Part) -> Option<&str> {
+        self.0.get(&(day, part)).map(|s| s.as_str())
+    }
+}
+
+/// Runs all solutions and prints a summary. Exits with a non-zero code if
+/// any of them panicked or timed out.
+///
+/// With `external`, the solvers in [`SOLVERS_PATH`] run too, and are
+/// compared against our answers in the summary. With `junit`, the results
+/// are also written there as a JUnit XML report, and with `artifacts`, the
+/// solutions' artifacts are written to that directory.
+pub fn run_all(
+    timeout: Option<Duration>,
+    use_cache: bool,
+    external: bool,
+    junit: Option<&str>,
+    artifacts: Option<&str>,
+) {
+    let results = run_with_cache(timeout, use_cache, artifacts.is_some());
+    let external = run_external(external, timeout);
+    let failed = print_summary(&results, None, &external);
+    if let Some(dir) = artifacts {
+        write_artifacts(dir, &results);
+    }
+    if let Some(path) = junit {
+        junit::write(path, "all", &results, None);
+    }
+    if failed > 0 {
+        std::process::exit(1);
+    }
+}
+
+/// Runs all solutions and compares them against the expected answers.
+/// Exits with a non-zero code on any wrong answer, panic or timeout.
+pub fn run_verify(
+    timeout: Option<Duration>,
+    use_cache: bool,
+    external: bool,
+    junit: Option<&str>,
+    artifacts: Option<&str>,
+) {
+    let answers = Answers::read(ANSWERS_PATH);
+    let results = run_with_cache(timeout, use_cache, artifacts.is_some());
+    let external = run_external(external, timeout);
+    let failed = print_summary(&results, Some(&answers), &external);
+    if let Some(dir) = artifacts {
+        write_artifacts(dir, &results);
+    }
+    if let Some(path) = junit {
+        junit::write(path, "verify", &results, Some(&answers));
+    }
+    solve_times::record_verified(&results, &answers);
+    if failed > 0 {
+        println!("{} of {} failed", failed, results.len());
+        std::process::exit(1);
+    }
+    println!("All {} verified", results.len());
+}
+
+/// Cached answers come without artifacts, so collecting them means solving
+/// everything again.
+fn run_with_cache(timeout: Option<Duration>, use_cache: bool, artifacts: bool) -> Vec<RunResult> {
+    if !use_cache || artifacts {
+        return runner::run_all(timeout, None, artifacts);
+    }
+    let mut cache = Cache::load(CACHE_PATH);
+    let results = runner::run_all(timeout, Some(&mut cache), false);
+    if let Err(e) = cache.save(CACHE_PATH) {
+        eprintln!("Couldn't save the answer cache to {}: {}", CACHE_PATH, e);
+    }
+    results
+}
+
+/// Writes every solution's artifacts to `dir` and lists them.
+fn write_artifacts(dir: &str, results: &[RunResult]) {
+    let mut paths = vec![];
+    for result in results {
+        match artifact::write(dir, result.day, result.part, &result.artifacts) {
+            Ok(written) => paths.extend(written),
+            Err(e) => eprintln!(
+                "Couldn't write the artifacts of {} {} to {}: {}",
+                result.day, result.part, dir, e
+            ),
+        }
+    }
+    println!("{} artifact(s) in {}", paths.len(), dir);
+    for path in paths {
+        println!("    {}", path);
+    }
+}
+
+fn run_external(enabled: bool, timeout: Option<Duration>) -> Vec<ExternalResult> {
+    if !enabled {
+        return vec![];
+    }
+    let solvers = external::read(SOLVERS_PATH).unwrap_or_else(|e| {
+        eprintln!("Couldn't read the external solvers: {}", e);
+        std::process::exit(1);
+    });
+    external::run_all(&solvers, timeout)
+}
+
+/// Prints one line per result, followed by the external solvers for the
+/// same day and part, returning how many of ours failed.
+fn print_summary(
+    results: &[RunResult],
+    answers: Option<&Answers>,
+    external: &[ExternalResult],
+) -> usize {
+    let mut failed = 0;
+    let mut disagreements = 0;
+    for result in results {
+        let expected = answers.and_then(|a| a.get(result.day, result.part));
+        let (status, is_failure) = status(&result.outcome, expected, answers.is_some());
+        if is_failure {
+            failed += 1;
+        }
+        println!(
+            "{} {}  {:<12} {:<20} {:>10}{}",
+            result.day,
+            result.part,
+            status,
+            result.outcome.answer().unwrap_or(""),
+            if result.cached {
+                "cached".to_string()
+            } else {
+                format_duration(result.duration)
+            },
+            result
+                .phases
+                .map_or(String::new(), |phases| format!("  ({})", phases))
+        );
+        match &result.outcome {
+            Outcome::Answer(answer) if expected.is_some_and(|e| e != answer) => {
+                println!("    expected: {}", expected.unwrap());
+            }
+            Outcome::Panicked(message) => println!("    panicked: {}", message),
+            _ => (),
+        }
+
+        for theirs in external
+            .iter()
+            .filter(|e| e.day == result.day && e.part == result.part)
+        {
+            let status = external_status(&result.outcome, &theirs.outcome);
+            if status == "DIFFERS" {
+                disagreements += 1;
+            }
+            println!(
+                "    {:<8} {:<12} {:<20} {:>10}  ({:.1}x ours)",
+                theirs.name,
+                status,
+                theirs.outcome.answer().unwrap_or(""),
+                format_duration(theirs.duration),
+                theirs.duration.as_secs_f64() / result.duration.as_secs_f64().max(1e-9)
+            );
+            if let Outcome::Panicked(message) = &theirs.outcome {
+                println!("        failed: {}", message);
+            }
+        }
+    }
+    if disagreements > 0 {
+        println!("{} external solver(s) disagree with us", disagreements);
+    }
+    failed
+}
+
+/// How an external solver's outcome compares to ours.
+fn external_status(ours: &Outcome, theirs: &Outcome) -> &'static str {
+    match (ours, theirs) {
+        (Outcome::Answer(ours), Outcome::Answer(theirs)) if ours == theirs => "agrees",
+        (Outcome::Answer(_), Outcome::Answer(_)) => "DIFFERS",
+        (_, Outcome::Answer(_)) => "answered",
+        (_, Outcome::NotSolved) => "not solved",
+        (_, Outcome::Panicked(_)) => "FAILED",
+        (_, Outcome::TimedOut) => "TIMED OUT",
+    }
+}
+
+fn status(outcome: &Outcome, expected: Option<&str>, verifying: bool) -> (&'static str, bool) {
+    match (outcome, expected) {
+        (Outcome::Answer(answer), Some(expected)) if answer == expected => ("ok", false),
+        (Outcome::Answer(_), Some(_)) => ("WRONG", true),
+        (Outcome::Answer(_), None) if verifying => ("unverified", false),
+        (Outcome::Answer(_), None) => ("solved", false),
+        (Outcome::NotSolved, _) => ("not solved", false),
+        (Outcome::Panicked(_), _) => ("PANICKED", true),
+        (Outcome::TimedOut, _) => ("TIMED OUT", true),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day::{Day, Part};
+    use crate::runner::Outcome;
+    use crate::verify::{external_status, status, Answers};
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = Answers::parse("# comment\nday01 part1 66306\nday10 part2 EGJBGCFK\n").unwrap();
+        assert_eq!(answers.get(Day::Day01, Part::Part1), Some("66306"));
+        assert_eq!(answers.get(Day::Day10, Part::Part2), Some("EGJBGCFK"));
+        assert_eq!(answers.get(Day::Day01, Part::Part2), None);
+        assert!(Answers::parse("day99 part1 1\n").is_err());
+        assert!(Answers::parse("day01\n").is_err());
+    }
+
+    #[test]
+    fn test_status() {
+        let answer = Outcome::Answer("42".to_string());
+        assert_eq!(status(&answer, Some("42"), true), ("ok", false));
+        assert_eq!(status(&answer, Some("41"), true), ("WRONG", true));
+        assert_eq!(status(&answer, None, true), ("unverified", false));
+        assert_eq!(status(&Outcome::NotSolved, None, true), ("not solved", false));
+        assert_eq!(status(&Outcome::TimedOut, None, false), ("TIMED OUT", true));
+    }
+
+    #[test]
+    fn test_external_status() {
+        let answer = |s: &str| Outcome::Answer(s.to_string());
+        assert_eq!(external_status(&answer("42"), &answer("42")), "agrees");
+        assert_eq!(external_status(&answer("42"), &answer("41")), "DIFFERS");
+        assert_eq!(external_status(&Outcome::NotSolved, &answer("41")), "answered");
+        assert_eq!(external_status(&answer("42"), &Outcome::TimedOut), "TIMED OUT");
+    }
+}
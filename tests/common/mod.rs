@@ -0,0 +1,23 @@
+//! Helpers shared by the integration tests.
+
+/// Each implemented day, and whether its feature is enabled for this build.
+const DAYS: &[(&str, bool)] = &[
+    ("day01", cfg!(feature = "day01")),
+    ("day02", cfg!(feature = "day02")),
+    ("day03", cfg!(feature = "day03")),
+    ("day04", cfg!(feature = "day04")),
+    ("day05", cfg!(feature = "day05")),
+    ("day06", cfg!(feature = "day06")),
+    ("day07", cfg!(feature = "day07")),
+    ("day08", cfg!(feature = "day08")),
+    ("day09", cfg!(feature = "day09")),
+    ("day10", cfg!(feature = "day10")),
+    ("day11", cfg!(feature = "day11")),
+];
+
+/// Whether the binary under test can run a day: it has to be implemented,
+/// and its feature has to be enabled for this build. Examples can be
+/// imported for days that are neither.
+pub fn is_compiled(day: &str) -> bool {
+    DAYS.iter().any(|&(name, compiled)| name == day && compiled)
+}
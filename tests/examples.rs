@@ -0,0 +1,52 @@
+//! Runs the binary on every example with an expected answer in
+//! `inputs/examples/answers.txt`, as added by `import-examples`.
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+const EXAMPLE_ANSWERS_PATH: &str = "inputs/examples/answers.txt";
+
+#[test]
+fn test_example_answers() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let answers =
+        fs::read_to_string(root.join(EXAMPLE_ANSWERS_PATH)).expect("Read the example answers");
+
+    let mut failures = vec![];
+    for line in answers.lines() {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.splitn(3, ' ');
+        let (Some(day), Some(part), Some(expected)) = (fields.next(), fields.next(), fields.next())
+        else {
+            panic!("Invalid line in {}: {:?}", EXAMPLE_ANSWERS_PATH, line);
+        };
+        if !common::is_compiled(day) {
+            continue;
+        }
+
+        let example = format!("inputs/examples/{}.txt", day);
+        let output = Command::new(env!("CARGO_BIN_EXE_adventofcode-2022-rust"))
+            .args([day, part, "--file", &example])
+            .current_dir(root)
+            .output()
+            .expect("Run the binary");
+        let stdout = String::from_utf8(output.stdout).expect("Output is valid UTF-8");
+        let answer = stdout
+            .lines()
+            .find_map(|line| line.strip_prefix(">>> answer: "))
+            .unwrap_or("");
+        if answer != expected {
+            failures.push(format!(
+                "{} {}: expected {}, got {:?}",
+                day, part, expected, answer
+            ));
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
@@ -0,0 +1,79 @@
+//! Runs the binary for every day and part on its example, comparing stdout
+//! to the snapshots in `tests/snapshots`.
+//!
+//! Changed output is written next to the snapshot as `.snap.new`, to be
+//! reviewed and accepted with `cargo run -- snapshots --accept`.
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+const EXAMPLES_DIR: &str = "inputs/examples";
+const SNAPSHOTS_DIR: &str = "tests/snapshots";
+
+/// Timings change from run to run, so they're left out of the snapshots.
+fn redact_timings(stdout: &str) -> String {
+    stdout
+        .lines()
+        .map(|line| {
+            if line.starts_with(">>> time:") {
+                ">>> time: ...\n".to_string()
+            } else {
+                format!("{}\n", line)
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn test_snapshots() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut examples: Vec<String> = fs::read_dir(root.join(EXAMPLES_DIR))
+        .expect("Read the examples dir")
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .filter(|name| name.starts_with("day"))
+        .filter_map(|name| name.strip_suffix(".txt").map(|day| day.to_string()))
+        .collect();
+    examples.sort();
+    assert!(!examples.is_empty(), "No examples in {}", EXAMPLES_DIR);
+    examples.retain(|day| common::is_compiled(day));
+
+    let mut failures = vec![];
+    for day in &examples {
+        for part in ["part1", "part2"] {
+            let example = format!("{}/{}.txt", EXAMPLES_DIR, day);
+            let output = Command::new(env!("CARGO_BIN_EXE_adventofcode-2022-rust"))
+                .args([day, part, "--file", &example])
+                .current_dir(root)
+                .output()
+                .expect("Run the binary");
+            let stdout = redact_timings(
+                &String::from_utf8(output.stdout).expect("Output is valid UTF-8"),
+            );
+
+            let snapshot = root.join(SNAPSHOTS_DIR).join(format!("{}_{}.snap", day, part));
+            let new_snapshot = snapshot.with_extension("snap.new");
+            match fs::read_to_string(&snapshot) {
+                Ok(expected) if expected == stdout => {
+                    let _ = fs::remove_file(&new_snapshot);
+                }
+                Ok(_) => {
+                    fs::write(&new_snapshot, &stdout).unwrap();
+                    failures.push(format!("{} {}: output changed", day, part));
+                }
+                Err(_) => {
+                    fs::write(&new_snapshot, &stdout).unwrap();
+                    failures.push(format!("{} {}: no snapshot yet", day, part));
+                }
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{}\nReview the changes with `cargo run -- snapshots`",
+        failures.join("\n")
+    );
+}